@@ -1,3 +1,5 @@
+// Requires `pkg-config` as a build-dependency (`[build-dependencies] pkg-config = "0.3"`
+// in Cargo.toml) for the `pkg_config::Config::new().probe("uhd")` discovery path below.
 use std::{
     env,
     path::{Path, PathBuf},
@@ -6,7 +8,9 @@ use std::{
 use bindgen::EnumVariation;
 
 pub fn main() {
-    for path in link_search_dirs() {
+    let uhd = discover_uhd();
+
+    for path in &uhd.link_search_dirs {
         println!("cargo:rustc-link-search={}", path.to_str().unwrap());
     }
 
@@ -17,31 +21,76 @@ pub fn main() {
     }
 
     let bindings_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("bindings.rs");
-    write_bindings(&bindings_path);
+    write_bindings(&bindings_path, &uhd.include_dirs);
 }
 
-pub fn write_bindings(path: &Path) {
-    let bindings = bindgen::Builder::default()
+pub fn write_bindings(path: &Path, include_dirs: &[PathBuf]) {
+    let mut builder = bindgen::Builder::default()
         .header("wrapper.h")
         .allowlist_item("uhd_.+")
         .default_enum_style(EnumVariation::ModuleConsts)
-        .derive_default(true)
-        .generate()
-        .expect("failed to generate bindings");
+        .derive_default(true);
+
+    for dir in include_dirs {
+        builder = builder.clang_arg(format!("-I{}", dir.display()));
+    }
+
+    let bindings = builder.generate().expect("failed to generate bindings");
 
     bindings
         .write_to_file(path)
         .expect("failed to write bindings.rs");
 }
 
-fn link_search_dirs() -> Vec<PathBuf> {
-    let mut dirs = vec![];
-    match target_os().as_deref() {
-        Some("linux") => dirs.extend([PathBuf::from("/usr/local/lib"), PathBuf::from("/usr/lib")]),
-        Some(n) => panic!("unsupported os: {n}"),
-        _ => panic!("unsupported os"),
+/// Where to find UHD's headers and libraries.
+struct UhdLibrary {
+    link_search_dirs: Vec<PathBuf>,
+    include_dirs: Vec<PathBuf>,
+}
+
+/// Locates the UHD library and headers, in order of preference:
+/// 1. `pkg-config` (honors its own cflags/libs, so non-standard installs
+///    that ship a `.pc` file just work).
+/// 2. The `UHD_LIB_DIR`/`UHD_INCLUDE_DIR` environment variables.
+/// 3. Platform-specific default install locations.
+fn discover_uhd() -> UhdLibrary {
+    if let Ok(lib) = pkg_config::Config::new().probe("uhd") {
+        return UhdLibrary {
+            link_search_dirs: lib.link_paths,
+            include_dirs: lib.include_paths,
+        };
+    }
+
+    let include_dirs = env::var("UHD_INCLUDE_DIR")
+        .map(|dir| vec![PathBuf::from(dir)])
+        .unwrap_or_default();
+
+    let link_search_dirs = match env::var("UHD_LIB_DIR") {
+        Ok(dir) => vec![PathBuf::from(dir)],
+        Err(_) => platform_default_lib_dirs(),
     };
-    dirs
+
+    UhdLibrary {
+        link_search_dirs,
+        include_dirs,
+    }
+}
+
+fn platform_default_lib_dirs() -> Vec<PathBuf> {
+    match target_os().as_deref() {
+        Some("linux") => vec![PathBuf::from("/usr/local/lib"), PathBuf::from("/usr/lib")],
+        Some("macos") => vec![
+            PathBuf::from("/opt/homebrew/lib"),
+            PathBuf::from("/usr/local/lib"),
+        ],
+        Some("windows") => env::var("UHD_PKG_PATH")
+            .map(|dir| vec![PathBuf::from(dir).join("lib")])
+            .unwrap_or_default(),
+        Some(other) => panic!(
+            "unsupported os: {other}; set UHD_LIB_DIR/UHD_INCLUDE_DIR to point at your UHD install"
+        ),
+        None => panic!("unsupported os; set UHD_LIB_DIR/UHD_INCLUDE_DIR to point at your UHD install"),
+    }
 }
 
 fn target_os() -> Option<String> {
@@ -0,0 +1,144 @@
+//! Host-side software automatic gain control.
+//!
+//! Only a handful of devices (the B200 series, E310, and E320) implement a
+//! hardware AGC; [`ChannelConfigurationBuilder::set_agc_enabled`](crate::ChannelConfigurationBuilder::set_agc_enabled)
+//! returns an error on everything else. [`SoftwareAgc`] provides an
+//! equivalent that runs entirely on the host, operating on the complex
+//! samples coming out of an [`RxStream`](crate::RxStream).
+//!
+//! It implements the classic attack/hang/decay envelope follower (in the
+//! style of Wheatley's `CAgc`): a fast "attack" pulls the level estimate up
+//! toward each stronger sample with the short attack time constant, a "hang" timer holds that
+//! estimate steady afterwards, and a slow "decay" lets it settle back down
+//! once the hang timer expires. The gain is applied to a delayed copy of
+//! the input (the delay approximately matches the attack time), so the
+//! loop has already reacted by the time a transient's leading edge reaches
+//! the output, which avoids overshoot clipping.
+
+use std::collections::VecDeque;
+
+use num_complex::Complex32;
+
+/// Tuning parameters for a [`SoftwareAgc`].
+///
+/// Levels and gain limits are expressed in dB, since envelope tracking in
+/// the log domain behaves far better than linear magnitude across the wide
+/// dynamic range an SDR front end can see.
+#[derive(Clone, Copy, Debug)]
+pub struct AgcConfig {
+    /// The level, in dB, the AGC tries to hold the output at.
+    pub target_level_db: f32,
+    /// How quickly the peak estimate rises to meet a louder sample, in seconds.
+    pub attack_secs: f64,
+    /// How long the peak estimate holds steady after a peak before decaying, in seconds.
+    pub hang_secs: f64,
+    /// How quickly the peak estimate falls back down once the hang timer expires, in seconds.
+    pub decay_secs: f64,
+    /// The minimum gain, in dB, the AGC is allowed to apply.
+    pub min_gain_db: f32,
+    /// The maximum gain, in dB, the AGC is allowed to apply.
+    pub max_gain_db: f32,
+}
+
+impl AgcConfig {
+    /// Reasonable defaults for a narrowband voice/data signal: a fast
+    /// attack, a short hang, and a slow decay.
+    pub fn new(target_level_db: f32) -> Self {
+        Self {
+            target_level_db,
+            attack_secs: 5e-4,
+            hang_secs: 5e-3,
+            decay_secs: 5e-2,
+            min_gain_db: -20.0,
+            max_gain_db: 60.0,
+        }
+    }
+}
+
+/// A software AGC (automatic gain control) that can be run over a stream of
+/// complex samples, for devices that don't implement one in hardware.
+///
+/// Construct one with the stream's sample rate, then call
+/// [`process_block`](Self::process_block) (or [`process_sample`](Self::process_sample))
+/// on each buffer received from an [`RxStream`](crate::RxStream).
+pub struct SoftwareAgc {
+    config: AgcConfig,
+    attack_coeff: f32,
+    decay_coeff: f32,
+    hang_samples: u64,
+    hang_counter: u64,
+    peak_db: f32,
+    gain: f32,
+    delay_line: VecDeque<Complex32>,
+    delay_len: usize,
+}
+
+impl SoftwareAgc {
+    /// Create a new software AGC for a stream sampled at `sample_rate` Hz.
+    pub fn new(config: AgcConfig, sample_rate: f64) -> Self {
+        let delay_len = (config.attack_secs * sample_rate).round().max(1.0) as usize;
+        Self {
+            attack_coeff: Self::time_const_to_coeff(config.attack_secs, sample_rate),
+            decay_coeff: Self::time_const_to_coeff(config.decay_secs, sample_rate),
+            hang_samples: (config.hang_secs * sample_rate).round() as u64,
+            hang_counter: 0,
+            peak_db: config.target_level_db,
+            gain: 1.0,
+            delay_line: VecDeque::with_capacity(delay_len + 1),
+            delay_len,
+            config,
+        }
+    }
+
+    fn time_const_to_coeff(time_const_secs: f64, sample_rate: f64) -> f32 {
+        if time_const_secs <= 0.0 || sample_rate <= 0.0 {
+            return 1.0;
+        }
+        (1.0 - (-1.0 / (time_const_secs * sample_rate)).exp()) as f32
+    }
+
+    /// The gain, as a linear scale factor, applied to the most recently
+    /// processed sample.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// The gain, in dB, applied to the most recently processed sample.
+    pub fn gain_db(&self) -> f32 {
+        20.0 * self.gain.log10()
+    }
+
+    /// Process one input sample, returning the gain-corrected, delay-compensated output.
+    pub fn process_sample(&mut self, input: Complex32) -> Complex32 {
+        let level_db = 20.0 * input.norm().max(f32::MIN_POSITIVE).log10();
+
+        if level_db > self.peak_db {
+            self.peak_db += self.attack_coeff * (level_db - self.peak_db);
+            self.hang_counter = self.hang_samples;
+        } else if self.hang_counter > 0 {
+            self.hang_counter -= 1;
+        } else {
+            self.peak_db += self.decay_coeff * (level_db - self.peak_db);
+        }
+
+        let gain_db = (self.config.target_level_db - self.peak_db)
+            .clamp(self.config.min_gain_db, self.config.max_gain_db);
+        self.gain = 10f32.powf(gain_db / 20.0);
+
+        self.delay_line.push_back(input);
+        let delayed = if self.delay_line.len() > self.delay_len {
+            self.delay_line.pop_front().unwrap()
+        } else {
+            Complex32::new(0.0, 0.0)
+        };
+
+        delayed * self.gain
+    }
+
+    /// Process a block of samples in place.
+    pub fn process_block(&mut self, samples: &mut [Complex32]) {
+        for sample in samples.iter_mut() {
+            *sample = self.process_sample(*sample);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    cell::{Cell, RefCell},
     fmt::Debug,
     mem::ManuallyDrop,
     ops::{Deref, Index, IndexMut},
@@ -16,6 +17,41 @@ pub trait SampleBuffer<S: Sample> {
     fn samples(&self) -> usize;
     fn as_ptr(&self) -> *const *const S;
     fn as_mut_ptr(&mut self) -> *mut *mut S;
+
+    /// Called after a receive call has written through
+    /// [`as_mut_ptr`](Self::as_mut_ptr), to give buffers whose
+    /// [`as_mut_ptr`](Self::as_mut_ptr) hands out a staging area (rather than a
+    /// pointer table into their own storage) a chance to copy the received
+    /// samples back into their real layout. A no-op for buffers (like
+    /// [`ArrayBuffer`]) whose pointer table already points straight at their
+    /// own storage.
+    fn post_recv_sync(&mut self) {}
+}
+
+/// Common read-only accessors shared by both sample buffer layouts
+/// ([`ArrayBuffer`]'s planar, one-contiguous-slice-per-channel layout and
+/// [`InterleavedBuffer`]'s single strided buffer), independent of how a
+/// channel's samples happen to be stored in memory.
+pub trait Frames<S> {
+    fn channels(&self) -> usize;
+    fn samples(&self) -> usize;
+
+    /// Iterate over one channel's samples, in sample order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel >= self.channels()`.
+    fn channel(&self, channel: usize) -> Box<dyn Iterator<Item = &S> + '_>;
+}
+
+/// Like [`Frames`], but for mutable access to a channel's samples.
+pub trait FramesMut<S>: Frames<S> {
+    /// Iterate mutably over one channel's samples, in sample order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel >= self.channels()`.
+    fn channel_mut(&mut self, channel: usize) -> Box<dyn Iterator<Item = &mut S> + '_>;
 }
 
 /// A slice `[S]` can be treated as a 1-channel [`SampleBuffer`] without requiring an additional
@@ -43,13 +79,45 @@ impl<S: Sample> SampleBuffer<S> for [S] {
 /// In many ways this type behaves as a `[&[S]]`. The first dimension is indexed by the
 /// channel number, while the second is indexed by the sample number.
 pub struct ArrayBuffer<S: Sample> {
-    /// Sample memory. Each `*mut S` is a leaked boxed slice whose length is equal to `samples`.
+    /// Sample memory. Each `*mut S` is a leaked `Vec<S>` of length `samples`
+    /// and capacity `capacities[channel]`.
     inner: Box<[*mut S]>,
+    /// The allocated capacity (in samples) backing each channel's pointer in
+    /// `inner`. Always `>= samples`; grown ahead of `samples` by
+    /// [`reserve`](Self::reserve) so a later [`resize`](Self::resize) up to
+    /// that point doesn't need to reallocate.
+    capacities: Box<[usize]>,
     channels: usize,
     samples: usize,
 }
 
+/// Leak a `Vec<S>`, returning a pointer to its buffer without shrinking its
+/// capacity down to its length (unlike `Box::leak(v.into_boxed_slice())`).
+fn leak_vec_ptr<S>(v: Vec<S>) -> *mut S {
+    ManuallyDrop::new(v).as_mut_ptr()
+}
+
 impl<S: Sample> ArrayBuffer<S> {
+    /// Copy this buffer into a frame-major [`InterleavedBuffer`], where
+    /// samples are laid out as `[ch0@0, ch1@0, …, ch0@1, ch1@1, …]` instead
+    /// of one contiguous allocation per channel.
+    pub fn interleave(&self) -> InterleavedBuffer<S>
+    where
+        S: Clone,
+    {
+        let mut data = Vec::with_capacity(self.channels * self.samples);
+        for frame in 0..self.samples {
+            for channel in self.iter() {
+                data.push(channel[frame].clone());
+            }
+        }
+        InterleavedBuffer {
+            data,
+            channels: self.channels,
+            scratch: RefCell::new(None),
+        }
+    }
+
     /// Creates a new `ArrayBuffer` with all samples initialized to the default sample value.
     pub fn new(channels: usize, samples: usize) -> Self
     where
@@ -70,6 +138,7 @@ impl<S: Sample> ArrayBuffer<S> {
                     Box::leak(v.into_boxed_slice()).as_mut_ptr()
                 })
                 .collect(),
+            capacities: vec![samples; channels].into_boxed_slice(),
             channels,
             samples,
         }
@@ -97,6 +166,7 @@ impl<S: Sample> ArrayBuffer<S> {
                     Box::leak(x.into_boxed_slice()).as_mut_ptr()
                 })
                 .collect(),
+            capacities: vec![samples; channels].into_boxed_slice(),
             channels,
             samples,
         }
@@ -136,6 +206,7 @@ impl<S: Sample> ArrayBuffer<S> {
                 .chunks(samples)
                 .map(|c| Box::leak(c.to_vec().into_boxed_slice()).as_mut_ptr())
                 .collect(),
+            capacities: vec![samples; channels].into_boxed_slice(),
             channels,
             samples,
         }
@@ -152,6 +223,7 @@ impl<S: Sample> ArrayBuffer<S> {
                 .into_iter()
                 .map(|c| Box::leak(c.into_boxed_slice()).as_mut_ptr())
                 .collect(),
+            capacities: vec![samples; channels].into_boxed_slice(),
             channels,
             samples,
         }
@@ -199,6 +271,64 @@ impl<S: Sample> ArrayBuffer<S> {
         self.iter_mut().map(|samples| samples.iter_mut()).flatten()
     }
 
+    /// Iterate column-wise, yielding a [`FrameRef`] for each sample index
+    /// that gives access to that sample across every channel.
+    ///
+    /// This has better cache locality than `iter_samples` for algorithms
+    /// that need to touch sample `j` of every channel together (mixing,
+    /// beamforming, phase alignment across channels).
+    pub fn iter_frames(&self) -> impl Iterator<Item = FrameRef<'_, S>> {
+        (0..self.samples).map(move |index| FrameRef {
+            ptrs: &self.inner,
+            index,
+        })
+    }
+
+    /// Like [`iter_frames`](Self::iter_frames), but gives mutable access to
+    /// the sample at that index in every channel.
+    pub fn iter_frames_mut(&self) -> impl Iterator<Item = FrameRefMut<'_, S>> {
+        (0..self.samples).map(move |index| FrameRefMut {
+            ptrs: &self.inner,
+            index,
+        })
+    }
+
+    /// Iterate over successive `block_len`-sample windows across every
+    /// channel, yielding a [`Block`] for each. The final block is truncated
+    /// if `samples` is not a multiple of `block_len`.
+    ///
+    /// Useful for running a fixed-size DSP transform (e.g. an FFT) over a
+    /// capture without copying samples out of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_len` is zero.
+    pub fn iter_blocks(&self, block_len: usize) -> impl Iterator<Item = Block<'_, S>> {
+        assert_ne!(block_len, 0, "block_len must be non-zero");
+        let samples = self.samples;
+        (0..samples).step_by(block_len).map(move |start| Block {
+            ptrs: &self.inner,
+            start,
+            len: block_len.min(samples - start),
+        })
+    }
+
+    /// Like [`iter_blocks`](Self::iter_blocks), but gives mutable access to
+    /// each block's samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_len` is zero.
+    pub fn iter_blocks_mut(&self, block_len: usize) -> impl Iterator<Item = BlockMut<'_, S>> {
+        assert_ne!(block_len, 0, "block_len must be non-zero");
+        let samples = self.samples;
+        (0..samples).step_by(block_len).map(move |start| BlockMut {
+            ptrs: &self.inner,
+            start,
+            len: block_len.min(samples - start),
+        })
+    }
+
     pub fn to_nested_vec(&self) -> Vec<Vec<S>>
     where
         S: Clone,
@@ -211,16 +341,90 @@ impl<S: Sample> ArrayBuffer<S> {
         let inner = std::mem::take(&mut shelf.inner);
         let v = inner
             .iter()
-            .map(|c| {
+            .zip(shelf.capacities.iter())
+            .map(|(c, &cap)| {
                 // SAFETY:
                 // - element type is the same before and after
                 // - all elements are initialized, unless `Self::new_uninit` was used
-                // - `Vec::into_boxed_slice` shrinks the capacity to len
-                unsafe { Vec::from_raw_parts(*c, shelf.samples, shelf.samples) }
+                // - `cap` is this channel's true allocated capacity
+                unsafe { Vec::from_raw_parts(*c, shelf.samples, cap) }
             })
             .collect();
         v
     }
+
+    /// Resize this buffer in place to the given channel and sample count,
+    /// filling any newly-added samples with the default sample value.
+    ///
+    /// Existing channels that are kept reuse their current allocation
+    /// (growing or shrinking it) rather than being freed and reallocated.
+    pub fn resize(&mut self, channels: usize, samples: usize)
+    where
+        S: Clone + Default,
+    {
+        self.resize_with(channels, samples, Default::default());
+    }
+
+    /// Like [`resize`](Self::resize), but fills any newly-added samples
+    /// with `fill` instead of the default sample value.
+    pub fn resize_with(&mut self, channels: usize, samples: usize, fill: S)
+    where
+        S: Clone,
+    {
+        let old_channels = self.channels;
+        let old_samples = self.samples;
+        let keep = channels.min(old_channels);
+
+        let mut new_inner = Vec::with_capacity(channels);
+        let mut new_capacities = Vec::with_capacity(channels);
+
+        for i in 0..keep {
+            // SAFETY: `self.inner[i]` backs a `Vec` of length `old_samples`
+            // and capacity `self.capacities[i]`.
+            let mut v =
+                unsafe { Vec::from_raw_parts(self.inner[i], old_samples, self.capacities[i]) };
+            v.resize(samples, fill.clone());
+            new_capacities.push(v.capacity());
+            new_inner.push(leak_vec_ptr(v));
+        }
+
+        for i in keep..old_channels {
+            // SAFETY: same as above; this channel is being dropped.
+            drop(unsafe {
+                Vec::from_raw_parts(self.inner[i], old_samples, self.capacities[i])
+            });
+        }
+
+        for _ in old_channels..channels {
+            let v = vec![fill.clone(); samples];
+            new_capacities.push(v.capacity());
+            new_inner.push(leak_vec_ptr(v));
+        }
+
+        self.inner = new_inner.into_boxed_slice();
+        self.capacities = new_capacities.into_boxed_slice();
+        self.channels = channels;
+        self.samples = samples;
+    }
+
+    /// Pre-grow the capacity of every channel so that a later
+    /// [`resize`](Self::resize)/[`resize_with`](Self::resize_with) up to
+    /// `samples() + extra_samples` total samples can reuse the existing
+    /// allocation instead of reallocating.
+    pub fn reserve(&mut self, extra_samples: usize) {
+        let target = self.samples + extra_samples;
+        for i in 0..self.channels {
+            if self.capacities[i] < target {
+                // SAFETY: `self.inner[i]` backs a `Vec` of length
+                // `self.samples` and capacity `self.capacities[i]`.
+                let mut v =
+                    unsafe { Vec::from_raw_parts(self.inner[i], self.samples, self.capacities[i]) };
+                v.reserve(target - v.len());
+                self.capacities[i] = v.capacity();
+                self.inner[i] = leak_vec_ptr(v);
+            }
+        }
+    }
 }
 
 impl<S> Drop for ArrayBuffer<S>
@@ -228,10 +432,10 @@ where
     S: Sample,
 {
     fn drop(&mut self) {
-        for i in self.inner.iter() {
-            unsafe {
-                let _ = Box::from_raw(i.cast::<&mut [S]>());
-            }
+        for (&ptr, &cap) in self.inner.iter().zip(self.capacities.iter()) {
+            // SAFETY: `ptr` backs a `Vec` of length `self.samples` and
+            // capacity `cap`.
+            drop(unsafe { Vec::from_raw_parts(ptr, self.samples, cap) });
         }
     }
 }
@@ -292,6 +496,30 @@ where
     }
 }
 
+impl<S: Sample> Frames<S> for ArrayBuffer<S> {
+    fn channels(&self) -> usize {
+        self.channels
+    }
+
+    fn samples(&self) -> usize {
+        self.samples
+    }
+
+    fn channel(&self, channel: usize) -> Box<dyn Iterator<Item = &S> + '_> {
+        Box::new(self.get(channel).expect("channel out of bounds").iter())
+    }
+}
+
+impl<S: Sample> FramesMut<S> for ArrayBuffer<S> {
+    fn channel_mut(&mut self, channel: usize) -> Box<dyn Iterator<Item = &mut S> + '_> {
+        Box::new(
+            self.get_mut(channel)
+                .expect("channel out of bounds")
+                .iter_mut(),
+        )
+    }
+}
+
 impl<S> Index<usize> for ArrayBuffer<S>
 where
     S: Sample,
@@ -312,58 +540,1053 @@ where
     }
 }
 
-#[cfg(test)]
-mod test_array_buff {
-    use num_complex::Complex32;
+/// A reference to one sample index across every channel of an [`ArrayBuffer`].
+///
+/// Yielded by [`ArrayBuffer::iter_frames`].
+pub struct FrameRef<'a, S> {
+    ptrs: &'a [*mut S],
+    index: usize,
+}
 
-    use crate::{ArrayBuffer, SampleBuffer};
+impl<'a, S> FrameRef<'a, S> {
+    /// The number of channels in this frame.
+    pub fn len(&self) -> usize {
+        self.ptrs.len()
+    }
 
-    fn check_fill(mut a: ArrayBuffer<i16>) {
-        a.iter_samples_mut()
-            .enumerate()
-            .for_each(|(i, s)| *s = i as i16);
-        for i in 0..(a.channels * a.samples) {
-            assert_eq!(a[i / a.samples][i % a.samples], i as i16);
+    pub fn is_empty(&self) -> bool {
+        self.ptrs.is_empty()
+    }
+
+    /// The sample from `channel` at this frame's sample index.
+    pub fn get(&self, channel: usize) -> Option<&'a S> {
+        self.ptrs
+            .get(channel)
+            .map(|&p| unsafe { &*p.add(self.index) })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a S> + '_ {
+        let index = self.index;
+        self.ptrs.iter().map(move |&p| unsafe { &*p.add(index) })
+    }
+}
+
+impl<'a, S> Index<usize> for FrameRef<'a, S> {
+    type Output = S;
+
+    fn index(&self, channel: usize) -> &S {
+        self.get(channel).expect("channel out of bounds")
+    }
+}
+
+/// Like [`FrameRef`], but gives mutable access to the sample at that index
+/// in every channel.
+///
+/// Yielded by [`ArrayBuffer::iter_frames_mut`].
+pub struct FrameRefMut<'a, S> {
+    ptrs: &'a [*mut S],
+    index: usize,
+}
+
+impl<'a, S> FrameRefMut<'a, S> {
+    /// The number of channels in this frame.
+    pub fn len(&self) -> usize {
+        self.ptrs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ptrs.is_empty()
+    }
+
+    /// The sample from `channel` at this frame's sample index.
+    pub fn get_mut(&mut self, channel: usize) -> Option<&mut S> {
+        self.ptrs
+            .get(channel)
+            .map(|&p| unsafe { &mut *p.add(self.index) })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut S> + '_ {
+        let index = self.index;
+        self.ptrs
+            .iter()
+            .map(move |&p| unsafe { &mut *p.add(index) })
+    }
+}
+
+impl<'a, S> Index<usize> for FrameRefMut<'a, S> {
+    type Output = S;
+
+    fn index(&self, channel: usize) -> &S {
+        self.ptrs
+            .get(channel)
+            .map(|&p| unsafe { &*p.add(self.index) })
+            .expect("channel out of bounds")
+    }
+}
+
+impl<'a, S> IndexMut<usize> for FrameRefMut<'a, S> {
+    fn index_mut(&mut self, channel: usize) -> &mut S {
+        self.get_mut(channel).expect("channel out of bounds")
+    }
+}
+
+/// A windowed view over every channel of an [`ArrayBuffer`], restricted to
+/// the sample range `[start, start + len)`.
+///
+/// Yielded by [`ArrayBuffer::iter_blocks`].
+pub struct Block<'a, S> {
+    ptrs: &'a [*mut S],
+    start: usize,
+    len: usize,
+}
+
+impl<'a, S> Block<'a, S> {
+    /// The number of channels in this block.
+    pub fn channels(&self) -> usize {
+        self.ptrs.len()
+    }
+
+    /// The number of samples in this block's window.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// This block's window of `channel`, as a slice of length `len()`.
+    pub fn channel(&self, channel: usize) -> Option<&'a [S]> {
+        let &ptr = self.ptrs.get(channel)?;
+        Some(unsafe { std::slice::from_raw_parts(ptr.add(self.start), self.len) })
+    }
+
+    /// Iterate over this block's window of every channel.
+    pub fn iter_channels(&self) -> impl Iterator<Item = &'a [S]> + '_ {
+        let (start, len) = (self.start, self.len);
+        self.ptrs
+            .iter()
+            .map(move |&ptr| unsafe { std::slice::from_raw_parts(ptr.add(start), len) })
+    }
+}
+
+impl<'a, S> Index<usize> for Block<'a, S> {
+    type Output = [S];
+
+    fn index(&self, channel: usize) -> &[S] {
+        self.channel(channel).expect("channel out of bounds")
+    }
+}
+
+/// Like [`Block`], but gives mutable access to each channel's window.
+///
+/// Yielded by [`ArrayBuffer::iter_blocks_mut`].
+pub struct BlockMut<'a, S> {
+    ptrs: &'a [*mut S],
+    start: usize,
+    len: usize,
+}
+
+impl<'a, S> BlockMut<'a, S> {
+    /// The number of channels in this block.
+    pub fn channels(&self) -> usize {
+        self.ptrs.len()
+    }
+
+    /// The number of samples in this block's window.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// This block's window of `channel`, as a mutable slice of length `len()`.
+    pub fn channel_mut(&mut self, channel: usize) -> Option<&'a mut [S]> {
+        let &ptr = self.ptrs.get(channel)?;
+        Some(unsafe { std::slice::from_raw_parts_mut(ptr.add(self.start), self.len) })
+    }
+
+    /// Iterate over this block's window of every channel.
+    pub fn iter_channels_mut(&mut self) -> impl Iterator<Item = &'a mut [S]> + '_ {
+        let (start, len) = (self.start, self.len);
+        self.ptrs
+            .iter()
+            .map(move |&ptr| unsafe { std::slice::from_raw_parts_mut(ptr.add(start), len) })
+    }
+}
+
+impl<'a, S> Index<usize> for BlockMut<'a, S> {
+    type Output = [S];
+
+    fn index(&self, channel: usize) -> &[S] {
+        let &ptr = self.ptrs.get(channel).expect("channel out of bounds");
+        unsafe { std::slice::from_raw_parts(ptr.add(self.start), self.len) }
+    }
+}
+
+impl<'a, S> IndexMut<usize> for BlockMut<'a, S> {
+    fn index_mut(&mut self, channel: usize) -> &mut [S] {
+        self.channel_mut(channel).expect("channel out of bounds")
+    }
+}
+
+/// A contiguous, frame-major sample buffer: samples are laid out as
+/// `[ch0@0, ch1@0, …, ch0@(n-1), ch1@(n-1)]` in a single allocation,
+/// indexed `frame * channels + channel`.
+///
+/// This is the layout most file formats (e.g. WAV) and DSP sinks expect,
+/// as opposed to [`ArrayBuffer`]'s channel-major layout of one allocation
+/// per channel. Convert between the two with
+/// [`ArrayBuffer::interleave`]/[`InterleavedBuffer::deinterleave`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterleavedBuffer<S: Sample> {
+    data: Vec<S>,
+    channels: usize,
+    /// Planar staging buffer used by the [`SampleBuffer`] impl, since UHD's
+    /// `*const *const S`/`*mut *mut S` pointer tables need one contiguous
+    /// array per channel, which interleaved data can't provide directly.
+    /// Rebuilt (reusing its allocation when the shape is unchanged) on
+    /// every [`as_ptr`](SampleBuffer::as_ptr)/[`as_mut_ptr`](SampleBuffer::as_mut_ptr) call.
+    scratch: RefCell<Option<ArrayBuffer<S>>>,
+}
+
+impl<S: Sample> InterleavedBuffer<S> {
+    /// Creates a new `InterleavedBuffer` with all samples initialized to the default sample value.
+    pub fn new(channels: usize, samples: usize) -> Self
+    where
+        S: Clone + Default,
+    {
+        Self {
+            data: vec![S::default(); channels * samples],
+            channels,
+            scratch: RefCell::new(None),
         }
     }
 
-    fn check_values(a: ArrayBuffer<i16>, s: impl Iterator<Item = i16>) {
-        assert!(a.iter_samples().zip(s).all(|(s1, s2)| *s1 == s2));
+    /// Creates a new `InterleavedBuffer` from already-interleaved data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data.len()` is not divisible by `channels`.
+    pub fn from_vec(channels: usize, data: Vec<S>) -> Self {
+        assert!(
+            channels == 0 || data.len() % channels == 0,
+            "mismatched channel buffer length"
+        );
+        Self {
+            data,
+            channels,
+            scratch: RefCell::new(None),
+        }
     }
 
-    #[test]
-    pub fn test_creation() {
-        check_fill(ArrayBuffer::new(3, 10));
-        check_fill(unsafe { ArrayBuffer::uninit(3, 10) });
-        check_values(ArrayBuffer::<i16>::from_iter(5, 0..100), 0..100);
-        check_values(ArrayBuffer::from_vec(5, (0..100).collect()), 0..100);
+    /// The number of channels in each frame.
+    pub fn channels(&self) -> usize {
+        self.channels
     }
 
-    #[test]
-    pub fn test_shape() {
-        let buff: ArrayBuffer<Complex32> = ArrayBuffer::new(10, 13);
-        assert_eq!(buff.channels(), 10);
-        assert_eq!(buff.samples(), 13);
-        assert_eq!(buff.inner.len(), 10);
-        assert!(buff.iter().all(|c| c.len() == 13))
+    /// The number of frames (one sample per channel) in this buffer.
+    pub fn len(&self) -> usize {
+        if self.channels == 0 {
+            0
+        } else {
+            self.data.len() / self.channels
+        }
     }
 
-    #[test]
-    pub fn test_iter() {
-        const CHANNELS: usize = 10;
-        const SAMPLES: usize = 13;
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-        let mut buff: ArrayBuffer<i16> = ArrayBuffer::new(CHANNELS, SAMPLES);
-        buff.iter_samples_mut()
-            .enumerate()
-            .for_each(|(i, s)| *s = i as i16);
-        check_values(buff, 0..(CHANNELS as i16 * SAMPLES as i16));
+    /// The frame at `index`, as a slice of length `channels`.
+    pub fn frame(&self, index: usize) -> &[S] {
+        let start = index * self.channels;
+        &self.data[start..start + self.channels]
     }
 
-    #[test]
-    pub fn test_clone() {
-        let buff: ArrayBuffer<i16> = ArrayBuffer::from_iter(5, 0..100);
-        let clone = buff.clone();
-        assert_eq!(buff, clone);
+    /// The frame at `index`, as a mutable slice of length `channels`.
+    pub fn frame_mut(&mut self, index: usize) -> &mut [S] {
+        let start = index * self.channels;
+        &mut self.data[start..start + self.channels]
+    }
+
+    /// Iterate over every frame, yielding a `&[S]` slice of length `channels`.
+    pub fn frames(&self) -> impl Iterator<Item = &[S]> {
+        self.data.chunks_exact(self.channels)
+    }
+
+    /// Iterate over every frame, yielding a `&mut [S]` slice of length `channels`.
+    pub fn frames_mut(&mut self) -> impl Iterator<Item = &mut [S]> {
+        self.data.chunks_exact_mut(self.channels)
+    }
+
+    /// The underlying contiguous interleaved sample data.
+    pub fn as_slice(&self) -> &[S] {
+        &self.data
+    }
+
+    /// The underlying contiguous interleaved sample data.
+    pub fn as_mut_slice(&mut self) -> &mut [S] {
+        &mut self.data
+    }
+
+    /// Consume this buffer, returning the underlying interleaved sample data.
+    pub fn into_vec(self) -> Vec<S> {
+        self.data
+    }
+
+    /// Copy this buffer into a channel-major [`ArrayBuffer`].
+    pub fn deinterleave(&self) -> ArrayBuffer<S>
+    where
+        S: Clone,
+    {
+        let mut out = unsafe { ArrayBuffer::uninit(self.channels, self.len()) };
+        for (frame_idx, frame) in self.frames().enumerate() {
+            for (channel, sample) in frame.iter().enumerate() {
+                out[channel][frame_idx] = sample.clone();
+            }
+        }
+        out
+    }
+
+    /// Alias for [`deinterleave`](Self::deinterleave), naming the
+    /// conversion in terms of [`Frames`] layouts (interleaved → planar)
+    /// rather than the interleaving operation itself.
+    pub fn to_planar(&self) -> ArrayBuffer<S>
+    where
+        S: Clone,
+    {
+        self.deinterleave()
+    }
+
+    /// Alias for [`ArrayBuffer::interleave`], naming the conversion in
+    /// terms of [`Frames`] layouts (planar → interleaved) rather than the
+    /// interleaving operation itself.
+    pub fn from_planar(buf: &ArrayBuffer<S>) -> Self
+    where
+        S: Clone,
+    {
+        buf.interleave()
+    }
+
+    /// Rebuild the planar scratch buffer from the current interleaved
+    /// data (reusing its allocation if the shape hasn't changed) and
+    /// return it, borrowed through the [`RefCell`].
+    fn rebuild_scratch(&self) -> std::cell::RefMut<'_, Option<ArrayBuffer<S>>>
+    where
+        S: Clone,
+    {
+        let mut scratch = self.scratch.borrow_mut();
+        let rebuild = match scratch.as_ref() {
+            Some(buf) => {
+                SampleBuffer::channels(buf) != self.channels
+                    || SampleBuffer::samples(buf) != self.len()
+            }
+            None => true,
+        };
+        if rebuild {
+            *scratch = Some(unsafe { ArrayBuffer::uninit(self.channels, self.len()) });
+        }
+        {
+            let buf = scratch.as_mut().unwrap();
+            for (frame_idx, frame) in self.frames().enumerate() {
+                for (channel, sample) in frame.iter().enumerate() {
+                    buf[channel][frame_idx] = sample.clone();
+                }
+            }
+        }
+        scratch
+    }
+
+    /// Copy the planar [`SampleBuffer`] scratch buffer back into this
+    /// buffer's interleaved layout.
+    ///
+    /// [`SampleBuffer::post_recv_sync`] calls this automatically after a
+    /// receive call writes through [`SampleBuffer::as_mut_ptr`], so callers
+    /// going through [`RxStreamReader::recv`](crate::usrp::RxStreamReader::recv)
+    /// and friends don't need to call it themselves. It's exposed for callers
+    /// driving [`SampleBuffer::as_mut_ptr`] directly (e.g. custom FFI calls)
+    /// who need the same reflect-back behavior. Not needed for sends, since
+    /// the scratch buffer is always rebuilt from `self` before use.
+    pub fn sync_from_scratch(&mut self)
+    where
+        S: Clone,
+    {
+        if let Some(scratch) = self.scratch.get_mut() {
+            for (frame_idx, frame) in self.data.chunks_exact_mut(self.channels).enumerate() {
+                for (channel, sample) in frame.iter_mut().enumerate() {
+                    *sample = scratch[channel][frame_idx].clone();
+                }
+            }
+        }
+    }
+}
+
+impl<S> SampleBuffer<S> for InterleavedBuffer<S>
+where
+    S: Sample + Clone,
+{
+    fn channels(&self) -> usize {
+        self.channels
+    }
+
+    fn samples(&self) -> usize {
+        self.len()
+    }
+
+    fn as_ptr(&self) -> *const *const S {
+        self.rebuild_scratch().as_ref().unwrap().as_ptr()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut *mut S {
+        self.rebuild_scratch().as_mut().unwrap().as_mut_ptr()
+    }
+
+    fn post_recv_sync(&mut self) {
+        self.sync_from_scratch();
+    }
+}
+
+impl<S: Sample> Frames<S> for InterleavedBuffer<S> {
+    fn channels(&self) -> usize {
+        self.channels
+    }
+
+    fn samples(&self) -> usize {
+        self.len()
+    }
+
+    fn channel(&self, channel: usize) -> Box<dyn Iterator<Item = &S> + '_> {
+        assert!(channel < self.channels, "channel out of bounds");
+        Box::new(self.data[channel..].iter().step_by(self.channels))
+    }
+}
+
+impl<S: Sample> FramesMut<S> for InterleavedBuffer<S> {
+    fn channel_mut(&mut self, channel: usize) -> Box<dyn Iterator<Item = &mut S> + '_> {
+        assert!(channel < self.channels, "channel out of bounds");
+        Box::new(self.data[channel..].iter_mut().step_by(self.channels))
+    }
+}
+
+/// Like [`ArrayBuffer`], but stores its `N` channel pointers inline in a
+/// `[*mut S; N]` array instead of a heap-allocated `Box<[*mut S]>`.
+///
+/// This avoids the extra allocation and indirection of `ArrayBuffer` for
+/// the common case of a known, fixed channel count, at the cost of `N`
+/// having to be known at compile time.
+pub struct StaticArrayBuffer<S: Sample, const N: usize> {
+    /// Sample memory. Each `*mut S` is a leaked boxed slice whose length is equal to `samples`.
+    inner: [*mut S; N],
+    samples: usize,
+}
+
+impl<S: Sample, const N: usize> StaticArrayBuffer<S, N> {
+    /// Creates a new `StaticArrayBuffer` with all samples initialized to the default sample value.
+    pub fn new(samples: usize) -> Self
+    where
+        S: Clone + Default,
+    {
+        Self::with_fill(samples, Default::default())
+    }
+
+    /// Creates a new `StaticArrayBuffer` with all samples initialized to the given fill value.
+    pub fn with_fill(samples: usize, fill: S) -> Self
+    where
+        S: Clone,
+    {
+        Self {
+            inner: std::array::from_fn(|_| {
+                let v = vec![fill.clone(); samples];
+                Box::leak(v.into_boxed_slice()).as_mut_ptr()
+            }),
+            samples,
+        }
+    }
+
+    /// Creates a new `StaticArrayBuffer` with uninitialized sample instances.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that appropriate portions of the buffer are initialized properly
+    /// before being used. Proper initialization could be receiving samples from a USRP or setting
+    /// necessary sample to a valid value.
+    pub unsafe fn uninit(samples: usize) -> Self {
+        Self {
+            inner: std::array::from_fn(|_| {
+                let mut x = Vec::with_capacity(samples);
+                unsafe { x.set_len(samples) };
+                Box::leak(x.into_boxed_slice()).as_mut_ptr()
+            }),
+            samples,
+        }
+    }
+
+    /// Create a new `StaticArrayBuffer` from `N` equal-length channel buffers.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the channels are not all the same length.
+    pub fn from_nested_vec(value: [Vec<S>; N]) -> Self {
+        let samples = value.first().map(|c| c.len()).unwrap_or(0);
+        if value.iter().skip(1).any(|c| c.len() != samples) {
+            panic!("mismatched channel buffer lengths")
+        }
+        Self {
+            inner: value.map(|c| Box::leak(c.into_boxed_slice()).as_mut_ptr()),
+            samples,
+        }
+    }
+
+    pub fn get(&self, channel: usize) -> Option<&[S]> {
+        Some(unsafe { std::slice::from_raw_parts(*self.inner.get(channel)?, self.samples) })
+    }
+
+    pub fn get_mut(&mut self, channel: usize) -> Option<&mut [S]> {
+        Some(unsafe { std::slice::from_raw_parts_mut(*self.inner.get(channel)?, self.samples) })
+    }
+
+    pub fn fill(&mut self, value: S)
+    where
+        S: Clone,
+    {
+        self.iter_samples_mut().for_each(|s| *s = value.clone());
+    }
+
+    pub fn fill_channel(&mut self, channel: usize, value: S)
+    where
+        S: Clone,
+    {
+        self[channel].iter_mut().for_each(|s| *s = value.clone());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &[S]> {
+        self.inner
+            .iter()
+            .map(|c| unsafe { std::slice::from_raw_parts(*c, self.samples) })
+    }
+
+    pub fn iter_mut(&self) -> impl Iterator<Item = &mut [S]> {
+        self.inner
+            .iter()
+            .map(|c| unsafe { std::slice::from_raw_parts_mut(*c, self.samples) })
+    }
+
+    pub fn iter_samples(&self) -> impl Iterator<Item = &S> {
+        self.iter().map(|samples| samples.iter()).flatten()
+    }
+
+    pub fn iter_samples_mut(&mut self) -> impl Iterator<Item = &mut S> {
+        self.iter_mut().map(|samples| samples.iter_mut()).flatten()
+    }
+
+    pub fn to_nested_vec(&self) -> Vec<Vec<S>>
+    where
+        S: Clone,
+    {
+        Vec::from_iter(self.iter().map(|c| c.to_vec()))
+    }
+}
+
+impl<S, const N: usize> Drop for StaticArrayBuffer<S, N>
+where
+    S: Sample,
+{
+    fn drop(&mut self) {
+        for &ptr in self.inner.iter() {
+            // SAFETY: `ptr` is a leaked boxed slice of length `self.samples`.
+            drop(unsafe { Vec::from_raw_parts(ptr, self.samples, self.samples) });
+        }
+    }
+}
+
+impl<S: Sample + Clone, const N: usize> Clone for StaticArrayBuffer<S, N> {
+    fn clone(&self) -> Self {
+        Self::from_nested_vec(self.to_nested_vec().try_into().unwrap_or_else(|_: Vec<Vec<S>>| {
+            unreachable!("to_nested_vec always yields exactly N channels")
+        }))
+    }
+}
+
+impl<S: Sample + PartialEq, const N: usize> PartialEq for StaticArrayBuffer<S, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.samples == other.samples && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+impl<S: Sample + Eq, const N: usize> Eq for StaticArrayBuffer<S, N> {}
+
+impl<S: Sample + Debug, const N: usize> Debug for StaticArrayBuffer<S, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StaticArrayBuffer")
+            .field("channels", &N)
+            .field("samples", &self.samples)
+            .finish()
+    }
+}
+
+impl<S, const N: usize> SampleBuffer<S> for StaticArrayBuffer<S, N>
+where
+    S: Sample,
+{
+    fn channels(&self) -> usize {
+        N
+    }
+
+    fn samples(&self) -> usize {
+        self.samples
+    }
+
+    fn as_ptr(&self) -> *const *const S {
+        self.inner.as_ptr().cast()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut *mut S {
+        self.inner.as_mut_ptr()
+    }
+}
+
+impl<S, const N: usize> Index<usize> for StaticArrayBuffer<S, N>
+where
+    S: Sample,
+{
+    type Output = [S];
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<S, const N: usize> IndexMut<usize> for StaticArrayBuffer<S, N>
+where
+    S: Sample,
+{
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+/// A fully stack-allocated sample buffer, with both the channel count and
+/// the samples-per-channel fixed at compile time.
+///
+/// Unlike [`StaticArrayBuffer`] (fixed channel count, but still one
+/// heap-allocated slice per channel), every sample lives inline in `self`
+/// as `[[S; SAMPLES]; CHANNELS]` — no allocation at all. UHD's
+/// `recv`/`send` still need a `*mut *mut S`/`*const *const S`
+/// channel-pointer table; since moving a `StackBuffer` would invalidate
+/// any pointers cached ahead of time, [`as_ptr`](SampleBuffer::as_ptr)/
+/// [`as_mut_ptr`](SampleBuffer::as_mut_ptr) instead (re)populate the table
+/// from `self`'s current address on every call.
+pub struct StackBuffer<S: Sample, const CHANNELS: usize, const SAMPLES: usize> {
+    data: [[S; SAMPLES]; CHANNELS],
+    ptrs: Cell<[*mut S; CHANNELS]>,
+}
+
+impl<S: Sample, const CHANNELS: usize, const SAMPLES: usize> StackBuffer<S, CHANNELS, SAMPLES> {
+    /// Creates a new `StackBuffer` with all samples initialized to the default sample value.
+    pub fn new() -> Self
+    where
+        S: Default,
+    {
+        Self {
+            data: std::array::from_fn(|_| std::array::from_fn(|_| S::default())),
+            ptrs: Cell::new([std::ptr::null_mut(); CHANNELS]),
+        }
+    }
+
+    /// Creates a new `StackBuffer` with uninitialized sample instances.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that appropriate portions of the buffer are initialized properly
+    /// before being used. Proper initialization could be receiving samples from a USRP or setting
+    /// necessary sample to a valid value.
+    pub unsafe fn uninit() -> Self {
+        Self {
+            data: unsafe { std::mem::MaybeUninit::uninit().assume_init() },
+            ptrs: Cell::new([std::ptr::null_mut(); CHANNELS]),
+        }
+    }
+
+    /// Create a new `StackBuffer` from `CHANNELS` equal-length channel arrays.
+    pub fn from_nested_array(value: [[S; SAMPLES]; CHANNELS]) -> Self {
+        Self {
+            data: value,
+            ptrs: Cell::new([std::ptr::null_mut(); CHANNELS]),
+        }
+    }
+
+    pub fn get(&self, channel: usize) -> Option<&[S; SAMPLES]> {
+        self.data.get(channel)
+    }
+
+    pub fn get_mut(&mut self, channel: usize) -> Option<&mut [S; SAMPLES]> {
+        self.data.get_mut(channel)
+    }
+
+    pub fn fill(&mut self, value: S)
+    where
+        S: Clone,
+    {
+        self.iter_samples_mut().for_each(|s| *s = value.clone());
+    }
+
+    pub fn fill_channel(&mut self, channel: usize, value: S)
+    where
+        S: Clone,
+    {
+        self[channel].iter_mut().for_each(|s| *s = value.clone());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &[S; SAMPLES]> {
+        self.data.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut [S; SAMPLES]> {
+        self.data.iter_mut()
+    }
+
+    pub fn iter_samples(&self) -> impl Iterator<Item = &S> {
+        self.data.iter().flatten()
+    }
+
+    pub fn iter_samples_mut(&mut self) -> impl Iterator<Item = &mut S> {
+        self.data.iter_mut().flatten()
+    }
+}
+
+impl<S: Sample + Clone, const CHANNELS: usize, const SAMPLES: usize> Clone
+    for StackBuffer<S, CHANNELS, SAMPLES>
+{
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            ptrs: Cell::new([std::ptr::null_mut(); CHANNELS]),
+        }
+    }
+}
+
+impl<S: Sample + PartialEq, const CHANNELS: usize, const SAMPLES: usize> PartialEq
+    for StackBuffer<S, CHANNELS, SAMPLES>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+impl<S: Sample + Eq, const CHANNELS: usize, const SAMPLES: usize> Eq
+    for StackBuffer<S, CHANNELS, SAMPLES>
+{
+}
+
+impl<S: Sample + Debug, const CHANNELS: usize, const SAMPLES: usize> Debug
+    for StackBuffer<S, CHANNELS, SAMPLES>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StackBuffer")
+            .field("channels", &CHANNELS)
+            .field("samples", &SAMPLES)
+            .finish()
+    }
+}
+
+impl<S, const CHANNELS: usize, const SAMPLES: usize> SampleBuffer<S>
+    for StackBuffer<S, CHANNELS, SAMPLES>
+where
+    S: Sample,
+{
+    fn channels(&self) -> usize {
+        CHANNELS
+    }
+
+    fn samples(&self) -> usize {
+        SAMPLES
+    }
+
+    fn as_ptr(&self) -> *const *const S {
+        let ptrs: [*mut S; CHANNELS] = std::array::from_fn(|i| self.data[i].as_ptr().cast_mut());
+        self.ptrs.set(ptrs);
+        self.ptrs.as_ptr().cast()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut *mut S {
+        let ptrs: [*mut S; CHANNELS] = std::array::from_fn(|i| self.data[i].as_mut_ptr());
+        self.ptrs.set(ptrs);
+        self.ptrs.as_ptr().cast()
+    }
+}
+
+impl<S: Sample, const CHANNELS: usize, const SAMPLES: usize> Frames<S>
+    for StackBuffer<S, CHANNELS, SAMPLES>
+{
+    fn channels(&self) -> usize {
+        CHANNELS
+    }
+
+    fn samples(&self) -> usize {
+        SAMPLES
+    }
+
+    fn channel(&self, channel: usize) -> Box<dyn Iterator<Item = &S> + '_> {
+        Box::new(self.get(channel).expect("channel out of bounds").iter())
+    }
+}
+
+impl<S: Sample, const CHANNELS: usize, const SAMPLES: usize> FramesMut<S>
+    for StackBuffer<S, CHANNELS, SAMPLES>
+{
+    fn channel_mut(&mut self, channel: usize) -> Box<dyn Iterator<Item = &mut S> + '_> {
+        Box::new(
+            self.get_mut(channel)
+                .expect("channel out of bounds")
+                .iter_mut(),
+        )
+    }
+}
+
+impl<S, const CHANNELS: usize, const SAMPLES: usize> Index<usize>
+    for StackBuffer<S, CHANNELS, SAMPLES>
+where
+    S: Sample,
+{
+    type Output = [S; SAMPLES];
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<S, const CHANNELS: usize, const SAMPLES: usize> IndexMut<usize>
+    for StackBuffer<S, CHANNELS, SAMPLES>
+where
+    S: Sample,
+{
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod test_array_buff {
+    use num_complex::Complex32;
+
+    use crate::{ArrayBuffer, InterleavedBuffer, SampleBuffer};
+
+    fn check_fill(mut a: ArrayBuffer<i16>) {
+        a.iter_samples_mut()
+            .enumerate()
+            .for_each(|(i, s)| *s = i as i16);
+        for i in 0..(a.channels * a.samples) {
+            assert_eq!(a[i / a.samples][i % a.samples], i as i16);
+        }
+    }
+
+    fn check_values(a: ArrayBuffer<i16>, s: impl Iterator<Item = i16>) {
+        assert!(a.iter_samples().zip(s).all(|(s1, s2)| *s1 == s2));
+    }
+
+    #[test]
+    pub fn test_creation() {
+        check_fill(ArrayBuffer::new(3, 10));
+        check_fill(unsafe { ArrayBuffer::uninit(3, 10) });
+        check_values(ArrayBuffer::<i16>::from_iter(5, 0..100), 0..100);
+        check_values(ArrayBuffer::from_vec(5, (0..100).collect()), 0..100);
+    }
+
+    #[test]
+    pub fn test_shape() {
+        let buff: ArrayBuffer<Complex32> = ArrayBuffer::new(10, 13);
+        assert_eq!(buff.channels(), 10);
+        assert_eq!(buff.samples(), 13);
+        assert_eq!(buff.inner.len(), 10);
+        assert!(buff.iter().all(|c| c.len() == 13))
+    }
+
+    #[test]
+    pub fn test_iter() {
+        const CHANNELS: usize = 10;
+        const SAMPLES: usize = 13;
+
+        let mut buff: ArrayBuffer<i16> = ArrayBuffer::new(CHANNELS, SAMPLES);
+        buff.iter_samples_mut()
+            .enumerate()
+            .for_each(|(i, s)| *s = i as i16);
+        check_values(buff, 0..(CHANNELS as i16 * SAMPLES as i16));
+    }
+
+    #[test]
+    pub fn test_clone() {
+        let buff: ArrayBuffer<i16> = ArrayBuffer::from_iter(5, 0..100);
+        let clone = buff.clone();
+        assert_eq!(buff, clone);
+    }
+
+    #[test]
+    pub fn test_interleave_roundtrip() {
+        let buff: ArrayBuffer<i16> = ArrayBuffer::from_nested_vec(vec![vec![0, 2, 4], vec![1, 3, 5]]);
+        let interleaved = buff.interleave();
+        assert_eq!(interleaved.channels(), 2);
+        assert_eq!(interleaved.len(), 3);
+        assert_eq!(interleaved.as_slice(), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(interleaved.deinterleave(), buff);
+    }
+
+    #[test]
+    pub fn test_interleaved_sample_buffer_roundtrip() {
+        let mut buff: InterleavedBuffer<i16> = InterleavedBuffer::from_vec(2, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(SampleBuffer::channels(&buff), 2);
+        assert_eq!(SampleBuffer::samples(&buff), 3);
+
+        let ptr = SampleBuffer::as_mut_ptr(&mut buff);
+        unsafe {
+            *(*ptr.add(0)).add(1) = 20;
+            *(*ptr.add(1)).add(1) = 30;
+        }
+        buff.sync_from_scratch();
+
+        assert_eq!(buff.frame(1), &[20, 30]);
+    }
+
+    #[test]
+    pub fn test_interleaved_post_recv_sync() {
+        let mut buff: InterleavedBuffer<i16> = InterleavedBuffer::from_vec(2, vec![0, 1, 2, 3, 4, 5]);
+
+        let ptr = SampleBuffer::as_mut_ptr(&mut buff);
+        unsafe {
+            *(*ptr.add(0)).add(1) = 20;
+            *(*ptr.add(1)).add(1) = 30;
+        }
+        // What `RxStreamReader::recv` calls automatically after UHD fills
+        // the scratch buffer — without it the new samples would stay
+        // invisible through `frame`/`as_slice`.
+        SampleBuffer::post_recv_sync(&mut buff);
+
+        assert_eq!(buff.frame(1), &[20, 30]);
+    }
+
+    #[test]
+    pub fn test_frames_planar_interleaved_conversion() {
+        let planar: ArrayBuffer<i16> = ArrayBuffer::from_nested_vec(vec![vec![0, 2, 4], vec![1, 3, 5]]);
+        let interleaved = InterleavedBuffer::from_planar(&planar);
+        assert_eq!(interleaved.as_slice(), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(interleaved.to_planar(), planar);
+
+        let planar_ch0: Vec<i16> = crate::Frames::channel(&planar, 0).copied().collect();
+        assert_eq!(planar_ch0, vec![0, 2, 4]);
+
+        let interleaved_ch1: Vec<i16> = crate::Frames::channel(&interleaved, 1).copied().collect();
+        assert_eq!(interleaved_ch1, vec![1, 3, 5]);
+    }
+
+    #[test]
+    pub fn test_iter_frames() {
+        let mut buff: ArrayBuffer<i16> =
+            ArrayBuffer::from_nested_vec(vec![vec![0, 2, 4], vec![1, 3, 5]]);
+        for frame in buff.iter_frames() {
+            assert_eq!(frame.len(), 2);
+            assert_eq!(frame[1] - frame[0], 1);
+        }
+        for mut frame in buff.iter_frames_mut() {
+            frame[0] *= 10;
+        }
+        assert_eq!(buff[0], [0, 20, 40]);
+    }
+
+    #[test]
+    pub fn test_resize_grow_and_shrink_samples() {
+        let mut buff: ArrayBuffer<i16> = ArrayBuffer::from_nested_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        buff.resize_with(2, 5, 0);
+        assert_eq!(buff.samples(), 5);
+        assert_eq!(buff[0], [1, 2, 3, 0, 0]);
+        assert_eq!(buff[1], [4, 5, 6, 0, 0]);
+
+        buff.resize(2, 2);
+        assert_eq!(buff.samples(), 2);
+        assert_eq!(buff[0], [1, 2]);
+        assert_eq!(buff[1], [4, 5]);
+    }
+
+    #[test]
+    pub fn test_resize_channels() {
+        let mut buff: ArrayBuffer<i16> = ArrayBuffer::new(2, 4);
+        buff.fill_channel(0, 1);
+        buff.fill_channel(1, 2);
+
+        buff.resize(3, 4);
+        assert_eq!(buff.channels(), 3);
+        assert_eq!(buff[2], [0, 0, 0, 0]);
+
+        buff.resize(1, 4);
+        assert_eq!(buff.channels(), 1);
+        assert_eq!(buff[0], [1, 1, 1, 1]);
+    }
+
+    #[test]
+    pub fn test_reserve_then_resize() {
+        let mut buff: ArrayBuffer<i16> = ArrayBuffer::from_iter(2, 0..10);
+        buff.reserve(20);
+        assert!(buff.capacities[0] >= 25);
+        buff.resize(2, 25);
+        assert_eq!(buff.samples(), 25);
+        assert_eq!(&buff[0][..5], &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    pub fn test_static_array_buffer() {
+        use crate::StaticArrayBuffer;
+
+        let mut buff: StaticArrayBuffer<i16, 2> =
+            StaticArrayBuffer::from_nested_vec([vec![0, 2, 4], vec![1, 3, 5]]);
+        assert_eq!(buff.channels(), 2);
+        assert_eq!(buff.samples(), 3);
+        assert_eq!(buff[0], [0, 2, 4]);
+        assert_eq!(buff[1], [1, 3, 5]);
+
+        buff.fill_channel(0, 9);
+        assert_eq!(buff[0], [9, 9, 9]);
+
+        let clone = buff.clone();
+        assert_eq!(buff, clone);
+    }
+
+    #[test]
+    pub fn test_stack_buffer() {
+        use crate::StackBuffer;
+
+        let mut buff: StackBuffer<i16, 2, 3> =
+            StackBuffer::from_nested_array([[0, 2, 4], [1, 3, 5]]);
+        assert_eq!(buff.channels(), 2);
+        assert_eq!(buff.samples(), 3);
+        assert_eq!(buff[0], [0, 2, 4]);
+        assert_eq!(buff[1], [1, 3, 5]);
+
+        buff.fill_channel(0, 9);
+        assert_eq!(buff[0], [9, 9, 9]);
+
+        let ptr = SampleBuffer::as_mut_ptr(&mut buff);
+        unsafe { *(*ptr.add(1)).add(0) = 100 };
+        assert_eq!(buff[1], [100, 3, 5]);
+
+        let clone = buff.clone();
+        assert_eq!(buff, clone);
+    }
+
+    #[test]
+    pub fn test_iter_blocks() {
+        let buff: ArrayBuffer<i16> = ArrayBuffer::from_nested_vec(vec![(0..5).collect(), (10..15).collect()]);
+
+        let blocks: Vec<_> = buff.iter_blocks(2).collect();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].channel(0), Some(&[0, 1][..]));
+        assert_eq!(blocks[0].channel(1), Some(&[10, 11][..]));
+        assert_eq!(blocks[2].len(), 1);
+        assert_eq!(blocks[2][0], [4]);
+
+        for mut block in buff.iter_blocks_mut(2) {
+            for ch in block.iter_channels_mut() {
+                ch.iter_mut().for_each(|s| *s *= 10);
+            }
+        }
+        assert_eq!(buff[0], [0, 10, 20, 30, 40]);
     }
 }
@@ -0,0 +1,141 @@
+//! A fixed-size pool of reusable sample buffers.
+//!
+//! Streaming recorders that hand samples off to another thread (e.g. a
+//! file writer) need a fresh buffer for every `recv`, but allocating and
+//! freeing a `Vec` thousands of times per second is a common source of
+//! dropped samples under load. [`BufferPool`] hands out pre-allocated
+//! buffers as RAII [`PooledBuffer`] guards that return their allocation to
+//! the pool on drop instead of freeing it.
+
+use std::{
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use crate::Sample;
+
+/// Sentinel marking the end of the free list.
+const NIL: usize = usize::MAX;
+
+struct Slot<S> {
+    buffer: UnsafeCell<Vec<S>>,
+    /// Index of the next free slot, or `NIL`. Only meaningful while this
+    /// slot is on the free list.
+    next: AtomicUsize,
+}
+
+/// A fixed-size pool of pre-allocated, fixed-capacity sample buffers.
+///
+/// Buffers are checked out and returned through a lock-free free list (a
+/// Treiber stack of slot indices), so checkout/release never blocks the
+/// hot RX path on a mutex. This is a simple index-based free list, not an
+/// ABA-hardened one — fine for the intended usage pattern of a small,
+/// fixed set of slots cycling between one RX thread and one writer
+/// thread, but it is not a general-purpose concurrent allocator.
+pub struct BufferPool<S> {
+    slots: Vec<Slot<S>>,
+    head: AtomicUsize,
+}
+
+// SAFETY: access to each slot's `UnsafeCell<Vec<S>>` is only ever granted
+// to the single `PooledBuffer` that currently owns that slot's index, and
+// the free-list CAS operations establish a happens-before edge between a
+// slot's release and its next checkout.
+unsafe impl<S: Send> Sync for BufferPool<S> {}
+
+impl<S> BufferPool<S> {
+    /// Create a pool of `count` buffers, each pre-allocated to `capacity`
+    /// samples and filled with the default sample value.
+    pub fn new(count: usize, capacity: usize) -> Arc<Self>
+    where
+        S: Sample + Clone + Default,
+    {
+        let slots = (0..count)
+            .map(|i| Slot {
+                buffer: UnsafeCell::new(vec![S::default(); capacity]),
+                next: AtomicUsize::new(if i + 1 < count { i + 1 } else { NIL }),
+            })
+            .collect();
+        Arc::new(Self {
+            slots,
+            head: AtomicUsize::new(if count > 0 { 0 } else { NIL }),
+        })
+    }
+
+    /// Check out a free buffer, or `None` if every buffer is currently
+    /// checked out.
+    pub fn checkout(self: &Arc<Self>) -> Option<PooledBuffer<S>> {
+        loop {
+            let head = self.head.load(Ordering::Acquire);
+            if head == NIL {
+                return None;
+            }
+            let next = self.slots[head].next.load(Ordering::Relaxed);
+            if self
+                .head
+                .compare_exchange_weak(head, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return Some(PooledBuffer {
+                    pool: self.clone(),
+                    index: head,
+                });
+            }
+        }
+    }
+
+    /// The total number of buffers in the pool.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn release(&self, index: usize) {
+        loop {
+            let head = self.head.load(Ordering::Acquire);
+            self.slots[index].next.store(head, Ordering::Relaxed);
+            if self
+                .head
+                .compare_exchange_weak(head, index, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                return;
+            }
+        }
+    }
+}
+
+/// An RAII guard for a [`BufferPool`] buffer, checked out via
+/// [`BufferPool::checkout`].
+///
+/// Dereferences to the underlying `Vec<S>`; the buffer is returned to the
+/// pool when this guard is dropped.
+pub struct PooledBuffer<S> {
+    pool: Arc<BufferPool<S>>,
+    index: usize,
+}
+
+impl<S> Deref for PooledBuffer<S> {
+    type Target = Vec<S>;
+
+    fn deref(&self) -> &Vec<S> {
+        // SAFETY: this guard uniquely owns `index` until it is dropped.
+        unsafe { &*self.pool.slots[self.index].buffer.get() }
+    }
+}
+
+impl<S> DerefMut for PooledBuffer<S> {
+    fn deref_mut(&mut self) -> &mut Vec<S> {
+        // SAFETY: this guard uniquely owns `index` until it is dropped.
+        unsafe { &mut *self.pool.slots[self.index].buffer.get() }
+    }
+}
+
+impl<S> Drop for PooledBuffer<S> {
+    fn drop(&mut self) {
+        self.pool.release(self.index);
+    }
+}
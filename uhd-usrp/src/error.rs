@@ -45,6 +45,15 @@ pub enum UhdError {
     Unknown = uhd_error::UHD_ERROR_UNKNOWN as u32,
 }
 
+/// Errors constructing a [`TimeSpec`](crate::TimeSpec).
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeError {
+    #[error("the given time cannot be represented without overflow")]
+    Overflow,
+    #[error("fractional seconds must be in the range [0, 1)")]
+    FracOutOfRange,
+}
+
 impl<T> Into<Result<T>> for UhdError {
     fn into(self) -> Result<T> {
         Err(self)
@@ -44,6 +44,30 @@ impl FfiString {
             .to_string_lossy()
             .into_owned())
     }
+
+    /// Call `f` with a buffer of `capacity` bytes, growing and retrying if
+    /// the string it wrote didn't fit (no null terminator within the
+    /// buffer), doubling the capacity each attempt up to `max_capacity`.
+    ///
+    /// Use this instead of a single hard-coded [`with_capacity`](Self::with_capacity)
+    /// call whenever the FFI getter can return strings of unbounded length.
+    pub fn get_with_retry(
+        mut capacity: usize,
+        max_capacity: usize,
+        mut f: impl FnMut(&mut FfiString) -> Result<()>,
+    ) -> Result<String> {
+        loop {
+            let mut s = FfiString::with_capacity(capacity);
+            f(&mut s)?;
+            match s.into_string() {
+                Ok(s) => return Ok(s),
+                Err(_) if capacity < max_capacity => {
+                    capacity = (capacity * 2).min(max_capacity);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
 }
 
 /// A vector of strings.
@@ -92,17 +116,17 @@ impl FfiStringVec {
     ///
     /// Returns `None` if the index is out of bounds.
     pub fn get(&self, index: usize) -> Option<String> {
-        let mut s = FfiString::with_capacity(128);
-        try_uhd!(unsafe {
-            uhd_usrp_sys::uhd_string_vector_at(
-                self.handle.as_mut_ptr(),
-                index,
-                s.as_mut_ptr(),
-                s.max_chars(),
-            )
+        FfiString::get_with_retry(128, 4096, |s| {
+            try_uhd!(unsafe {
+                uhd_usrp_sys::uhd_string_vector_at(
+                    self.handle.as_mut_ptr(),
+                    index,
+                    s.as_mut_ptr(),
+                    s.max_chars(),
+                )
+            })
         })
-        .ok()?;
-        s.into_string().ok()
+        .ok()
     }
 
     /// Convert this type to a Rust [`Vec`].
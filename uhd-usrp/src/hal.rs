@@ -0,0 +1,118 @@
+//! Hardware-abstraction traits over streaming and device configuration.
+//!
+//! [`TxSink`] and [`RxSource`] cover the send/recv surface of
+//! [`TxStream`](crate::TxStream)/[`RxStream`](crate::RxStream), and
+//! [`UsrpDevice`] covers the channel-count/stream-opening surface of
+//! [`Usrp`](crate::Usrp). Writing DSP code against these traits instead of
+//! the concrete types lets it be unit-tested against an in-memory fake
+//! device (recording what was transmitted, replaying canned receive
+//! buffers) without a physical radio or the UHD shared library present.
+
+use std::time::Duration;
+
+use crate::{Result, Sample, TxStream, RxStream, Usrp};
+
+/// A sink that consumes transmit samples, implemented by [`TxStream`].
+pub trait TxSink<T: Sample> {
+    /// The maximum number of samples per channel that can be sent in a
+    /// single call to [`send`](TxSink::send).
+    fn max_samples_per_buffer(&self) -> usize;
+    /// The number of channels this sink was opened with.
+    fn channels(&self) -> usize;
+    /// Send one slice of samples per channel, blocking for up to `timeout`.
+    ///
+    /// Returns the number of samples sent per channel.
+    fn send(&mut self, buffers: &[&[T]], timeout: Duration) -> Result<usize>;
+}
+
+/// A source that produces receive samples, implemented by [`RxStream`].
+pub trait RxSource<T: Sample> {
+    /// The maximum number of samples per channel that can be received in a
+    /// single call to [`recv`](RxSource::recv).
+    fn max_samples_per_buffer(&self) -> usize;
+    /// The number of channels this source was opened with.
+    fn channels(&self) -> usize;
+    /// Fill one slice of samples per channel, blocking for up to `timeout`.
+    ///
+    /// Returns the number of samples received per channel.
+    fn recv(&mut self, buffers: &mut [&mut [T]], timeout: Duration) -> Result<usize>;
+}
+
+/// The subset of [`Usrp`]'s configuration/stream-opening surface needed to
+/// write DSP code that's generic over the concrete device.
+pub trait UsrpDevice {
+    /// The concrete [`TxSink`] opened by [`open_tx_stream`](UsrpDevice::open_tx_stream).
+    type TxStream<T: Sample>: TxSink<T>;
+    /// The concrete [`RxSource`] opened by [`open_rx_stream`](UsrpDevice::open_rx_stream).
+    type RxStream<T: Sample>: RxSource<T>;
+
+    /// Get the total number of RX channels on this device.
+    fn rx_channels(&self) -> Result<usize>;
+    /// Get the total number of TX channels on this device.
+    fn tx_channels(&self) -> Result<usize>;
+    /// Open an RX stream over the given channels.
+    fn open_rx_stream<T: Sample>(&self, channels: &[usize]) -> Result<Self::RxStream<T>>;
+    /// Open a TX stream over the given channels.
+    fn open_tx_stream<T: Sample>(&self, channels: &[usize]) -> Result<Self::TxStream<T>>;
+}
+
+impl<T: Sample> TxSink<T> for TxStream<T> {
+    fn max_samples_per_buffer(&self) -> usize {
+        self.max_samples_per_channel()
+    }
+
+    fn channels(&self) -> usize {
+        TxStream::channels(self)
+    }
+
+    fn send(&mut self, buffers: &[&[T]], timeout: Duration) -> Result<usize> {
+        let samples = buffers.first().map_or(0, |b| b.len());
+        let ptrs: Vec<*const T> = buffers.iter().map(|b| b.as_ptr()).collect();
+        unsafe {
+            self.writer()
+                .with_timeout(timeout)
+                .send_raw(ptrs.as_ptr(), samples)
+        }
+    }
+}
+
+impl<T: Sample> RxSource<T> for RxStream<T> {
+    fn max_samples_per_buffer(&self) -> usize {
+        self.max_samples_per_channel()
+    }
+
+    fn channels(&self) -> usize {
+        RxStream::channels(self)
+    }
+
+    fn recv(&mut self, buffers: &mut [&mut [T]], timeout: Duration) -> Result<usize> {
+        let samples = buffers.first().map_or(0, |b| b.len());
+        let mut ptrs: Vec<*mut T> = buffers.iter_mut().map(|b| b.as_mut_ptr()).collect();
+        unsafe {
+            self.reader()
+                .with_timeout(timeout)
+                .recv_raw(ptrs.as_mut_ptr(), samples)
+        }
+    }
+}
+
+impl UsrpDevice for Usrp {
+    type TxStream<T: Sample> = TxStream<T>;
+    type RxStream<T: Sample> = RxStream<T>;
+
+    fn rx_channels(&self) -> Result<usize> {
+        Usrp::rx_channels(self)
+    }
+
+    fn tx_channels(&self) -> Result<usize> {
+        Usrp::tx_channels(self)
+    }
+
+    fn open_rx_stream<T: Sample>(&self, channels: &[usize]) -> Result<RxStream<T>> {
+        self.rx_stream::<T>().with_channels(channels).open()
+    }
+
+    fn open_tx_stream<T: Sample>(&self, channels: &[usize]) -> Result<TxStream<T>> {
+        self.tx_stream::<T>().with_channels(channels).open()
+    }
+}
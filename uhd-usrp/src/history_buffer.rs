@@ -0,0 +1,185 @@
+//! A fixed-capacity ring buffer retaining recent per-channel samples.
+//!
+//! Block-based DSP (FIR filtering, FFT overlap-add/save) run over a
+//! continuous RX stream needs to prepend a tail of samples from the
+//! previous block onto the next one. [`HistoryBuffer`] keeps one ring per
+//! channel and assembles that prepended buffer directly, so callers never
+//! have to stitch blocks together by hand.
+
+use crate::{ArrayBuffer, Sample, SampleBuffer};
+
+struct ChannelHistory<S> {
+    data: Vec<S>,
+    /// Index the next pushed sample will occupy.
+    cursor: usize,
+    filled: bool,
+}
+
+impl<S: Clone> ChannelHistory<S> {
+    fn push(&mut self, sample: S) {
+        let capacity = self.data.len();
+        self.data[self.cursor] = sample;
+        self.cursor += 1;
+        if self.cursor == capacity {
+            self.cursor = 0;
+            self.filled = true;
+        }
+    }
+
+    /// Yield this channel's retained samples in chronological order
+    /// (oldest first).
+    fn ordered(&self) -> impl Iterator<Item = &S> {
+        let capacity = self.data.len();
+        let (start, len) = if self.filled {
+            (self.cursor, capacity)
+        } else {
+            (0, self.cursor)
+        };
+        (0..len).map(move |i| &self.data[(start + i) % capacity])
+    }
+}
+
+/// A per-channel ring buffer retaining the last `capacity` samples
+/// received for each channel.
+///
+/// Until a channel's ring has filled, [`ordered`](Self::ordered)/
+/// [`recent`](Self::recent) only return the samples actually pushed so
+/// far for that channel — never uninitialized ring slots.
+pub struct HistoryBuffer<S> {
+    capacity: usize,
+    channels: Vec<ChannelHistory<S>>,
+}
+
+impl<S: Sample + Clone + Default> HistoryBuffer<S> {
+    /// Create a new `HistoryBuffer` retaining up to `capacity` samples of
+    /// history for each of `channels` channels.
+    pub fn new(channels: usize, capacity: usize) -> Self {
+        Self {
+            capacity,
+            channels: (0..channels)
+                .map(|_| ChannelHistory {
+                    data: vec![S::default(); capacity],
+                    cursor: 0,
+                    filled: false,
+                })
+                .collect(),
+        }
+    }
+
+    /// The number of channels this buffer retains history for.
+    pub fn channels(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// The maximum number of samples retained per channel.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Append a newly received block's samples onto each channel's ring,
+    /// overwriting the oldest samples once a channel's ring has filled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block.channels() != self.channels()`.
+    pub fn push_block(&mut self, block: &ArrayBuffer<S>) {
+        assert_eq!(
+            block.channels(),
+            self.channels.len(),
+            "block channel count does not match history buffer"
+        );
+        for (history, samples) in self.channels.iter_mut().zip(block.iter()) {
+            for sample in samples {
+                history.push(sample.clone());
+            }
+        }
+    }
+
+    /// Iterate over `channel`'s retained history, in chronological order
+    /// (oldest first).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel >= self.channels()`.
+    pub fn ordered(&self, channel: usize) -> impl Iterator<Item = &S> {
+        self.channels[channel].ordered()
+    }
+
+    /// The last `n` samples retained for `channel`, in chronological
+    /// order (oldest first), regardless of the ring's internal write
+    /// cursor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel >= self.channels()`, or if fewer than `n`
+    /// samples have been retained for that channel yet.
+    pub fn recent(&self, channel: usize, n: usize) -> impl Iterator<Item = &S> {
+        let history = &self.channels[channel];
+        let available = history.ordered().count();
+        assert!(
+            n <= available,
+            "requested more samples than have been retained"
+        );
+        history.ordered().skip(available - n)
+    }
+
+    /// Build a contiguous per-channel buffer of `history + new_block.samples()`
+    /// samples — the last `history` samples retained for each channel,
+    /// followed by `new_block`'s own samples — ready for a block-based DSP
+    /// stage doing overlap-save, then push `new_block` onto the ring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_block.channels() != self.channels()`, or if fewer
+    /// than `history` samples have been retained yet for any channel.
+    pub fn as_overlap_input(
+        &mut self,
+        history: usize,
+        new_block: &ArrayBuffer<S>,
+    ) -> ArrayBuffer<S> {
+        assert_eq!(
+            new_block.channels(),
+            self.channels.len(),
+            "block channel count does not match history buffer"
+        );
+
+        let new_samples = new_block.samples();
+        let mut out = ArrayBuffer::new(self.channels.len(), history + new_samples);
+        for channel in 0..self.channels.len() {
+            let tail: Vec<S> = self.recent(channel, history).cloned().collect();
+            let out_channel = &mut out[channel];
+            out_channel[..history].clone_from_slice(&tail);
+            out_channel[history..].clone_from_slice(new_block.get(channel).unwrap());
+        }
+
+        self.push_block(new_block);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HistoryBuffer;
+    use crate::ArrayBuffer;
+
+    #[test]
+    pub fn test_push_and_ordered() {
+        let mut history: HistoryBuffer<i16> = HistoryBuffer::new(1, 4);
+        history.push_block(&ArrayBuffer::from_nested_vec(vec![vec![1, 2]]));
+        assert_eq!(history.ordered(0).copied().collect::<Vec<_>>(), [1, 2]);
+
+        history.push_block(&ArrayBuffer::from_nested_vec(vec![vec![3, 4, 5]]));
+        assert_eq!(history.ordered(0).copied().collect::<Vec<_>>(), [2, 3, 4, 5]);
+        assert_eq!(history.recent(0, 2).copied().collect::<Vec<_>>(), [4, 5]);
+    }
+
+    #[test]
+    pub fn test_as_overlap_input() {
+        let mut history: HistoryBuffer<i16> = HistoryBuffer::new(1, 4);
+        history.push_block(&ArrayBuffer::from_nested_vec(vec![vec![1, 2, 3, 4]]));
+
+        let new_block = ArrayBuffer::from_nested_vec(vec![vec![5, 6]]);
+        let overlapped = history.as_overlap_input(2, &new_block);
+        assert_eq!(overlapped[0], [3, 4, 5, 6]);
+    }
+}
@@ -0,0 +1,327 @@
+//! Recording and replaying raw sample buffers to/from disk.
+//!
+//! Unlike [`RecordSink`](crate::RecordSink)'s self-describing SigMF/WAV
+//! containers (meant for interop with other tools), this module uses a
+//! compact, flat binary encoding in the style of the `bitcode` crate: a
+//! small fixed header (channel count, samples per channel, sample type
+//! tag, on-disk layout, and optionally the `sample_rate`/`center_freq` the
+//! capture was taken at) followed by the raw sample payload with no
+//! further framing. [`ArrayBuffer::write_to`]/[`ArrayBuffer::read_from`]
+//! round-trip a buffer through this format, letting a capture be replayed
+//! deterministically through the transmit API or offline analysis.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::{ArrayBuffer, Frames, InterleavedBuffer, Sample};
+
+const MAGIC: &[u8; 4] = b"UIQF";
+const VERSION: u8 = 1;
+
+/// Capture-time metadata optionally stored alongside an IQ recording's
+/// sample payload.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CaptureMeta {
+    /// The sample rate the capture was taken at, in Hz.
+    pub sample_rate: Option<f64>,
+    /// The center frequency the capture was taken at, in Hz.
+    pub center_freq: Option<f64>,
+}
+
+impl CaptureMeta {
+    /// Metadata carrying the `sample_rate`/`center_freq` a channel was
+    /// configured with at the time of capture.
+    pub fn new(sample_rate: f64, center_freq: f64) -> Self {
+        Self {
+            sample_rate: Some(sample_rate),
+            center_freq: Some(center_freq),
+        }
+    }
+}
+
+/// The on-disk sample layout of a UIQF recording.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum IqLayout {
+    /// One contiguous run per channel (`c0s0, c0s1, …, c1s0, c1s1, …`).
+    Planar,
+    /// Samples interleaved frame-major (`c0s0, c1s0, c0s1, c1s1, …`).
+    Interleaved,
+}
+
+struct Header {
+    layout: IqLayout,
+    type_tag: String,
+    channels: usize,
+    samples_per_channel: usize,
+    meta: CaptureMeta,
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+fn write_optional_f64(w: &mut impl Write, value: Option<f64>) -> io::Result<()> {
+    match value {
+        Some(v) => {
+            w.write_all(&[1])?;
+            w.write_all(&v.to_le_bytes())
+        }
+        None => w.write_all(&[0]),
+    }
+}
+
+fn read_optional_f64(r: &mut impl Read) -> io::Result<Option<f64>> {
+    let mut present = [0u8; 1];
+    r.read_exact(&mut present)?;
+    if present[0] == 0 {
+        return Ok(None);
+    }
+    let mut bytes = [0u8; 8];
+    r.read_exact(&mut bytes)?;
+    Ok(Some(f64::from_le_bytes(bytes)))
+}
+
+fn write_header(
+    w: &mut impl Write,
+    layout: IqLayout,
+    type_tag: &str,
+    channels: usize,
+    samples_per_channel: usize,
+    meta: CaptureMeta,
+) -> io::Result<()> {
+    w.write_all(MAGIC)?;
+    w.write_all(&[VERSION])?;
+    w.write_all(&[match layout {
+        IqLayout::Planar => 0,
+        IqLayout::Interleaved => 1,
+    }])?;
+    w.write_all(&[type_tag.len() as u8])?;
+    w.write_all(type_tag.as_bytes())?;
+    w.write_all(&(channels as u32).to_le_bytes())?;
+    w.write_all(&(samples_per_channel as u64).to_le_bytes())?;
+    write_optional_f64(w, meta.sample_rate)?;
+    write_optional_f64(w, meta.center_freq)
+}
+
+fn read_header(r: &mut impl Read) -> io::Result<Header> {
+    let mut magic = [0u8; 4];
+    r.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid_data("not a UIQF recording"));
+    }
+    let mut version = [0u8; 1];
+    r.read_exact(&mut version)?;
+    if version[0] != VERSION {
+        return Err(invalid_data(format!("unsupported UIQF version {}", version[0])));
+    }
+    let mut layout = [0u8; 1];
+    r.read_exact(&mut layout)?;
+    let layout = match layout[0] {
+        0 => IqLayout::Planar,
+        1 => IqLayout::Interleaved,
+        other => return Err(invalid_data(format!("unknown UIQF layout tag {other}"))),
+    };
+    let mut tag_len = [0u8; 1];
+    r.read_exact(&mut tag_len)?;
+    let mut tag_bytes = vec![0u8; tag_len[0] as usize];
+    r.read_exact(&mut tag_bytes)?;
+    let type_tag =
+        String::from_utf8(tag_bytes).map_err(|_| invalid_data("type tag is not valid UTF-8"))?;
+    let mut channels = [0u8; 4];
+    r.read_exact(&mut channels)?;
+    let channels = u32::from_le_bytes(channels) as usize;
+    let mut samples_per_channel = [0u8; 8];
+    r.read_exact(&mut samples_per_channel)?;
+    let samples_per_channel = u64::from_le_bytes(samples_per_channel) as usize;
+    let meta = CaptureMeta {
+        sample_rate: read_optional_f64(r)?,
+        center_freq: read_optional_f64(r)?,
+    };
+    Ok(Header {
+        layout,
+        type_tag,
+        channels,
+        samples_per_channel,
+        meta,
+    })
+}
+
+fn write_samples<S>(w: &mut impl Write, samples: &[S]) -> io::Result<()> {
+    // Safety: `samples` is a valid, initialized slice; we only read the
+    // memory it already owns, reinterpreted as bytes.
+    let bytes = unsafe {
+        std::slice::from_raw_parts(samples.as_ptr().cast::<u8>(), std::mem::size_of_val(samples))
+    };
+    w.write_all(bytes)
+}
+
+fn read_samples<S>(r: &mut impl Read, count: usize) -> io::Result<Vec<S>> {
+    let mut bytes = vec![0u8; count * std::mem::size_of::<S>()];
+    r.read_exact(&mut bytes)?;
+    let mut out = Vec::<S>::with_capacity(count);
+    // Safety: `bytes` holds exactly `count * size_of::<S>()` bytes read
+    // verbatim from a buffer this same module wrote with `write_samples`,
+    // so reinterpreting it as `count` initialized `S`s is valid as long as
+    // the caller wrote (and we're reading back) the same sample type.
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), out.as_mut_ptr().cast::<u8>(), bytes.len());
+        out.set_len(count);
+    }
+    Ok(out)
+}
+
+impl<S: Sample> ArrayBuffer<S> {
+    /// Write this buffer to `path` in the compact UIQF binary format,
+    /// alongside `meta` captured at record time.
+    ///
+    /// Samples are written planar (one contiguous run per channel),
+    /// matching this buffer's own in-memory layout directly.
+    pub fn write_to(&self, path: impl AsRef<Path>, meta: CaptureMeta) -> io::Result<()> {
+        let mut w = BufWriter::new(File::create(path)?);
+        write_header(
+            &mut w,
+            IqLayout::Planar,
+            S::name(),
+            Frames::channels(self),
+            Frames::samples(self),
+            meta,
+        )?;
+        for channel in self.iter() {
+            write_samples(&mut w, channel)?;
+        }
+        w.flush()
+    }
+
+    /// Read a UIQF recording written by [`write_to`](Self::write_to) or
+    /// [`InterleavedBuffer::write_to`], returning the decoded buffer
+    /// (converted to planar layout if it was recorded interleaved)
+    /// alongside the [`CaptureMeta`] it was recorded with.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `path` isn't a UIQF recording, or was written for a
+    /// different sample type than `S`.
+    pub fn read_from(path: impl AsRef<Path>) -> io::Result<(Self, CaptureMeta)>
+    where
+        S: Clone,
+    {
+        let mut r = BufReader::new(File::open(path)?);
+        let header = read_header(&mut r)?;
+        if header.type_tag != S::name() {
+            return Err(invalid_data(format!(
+                "recording holds {} samples, not {}",
+                header.type_tag,
+                S::name()
+            )));
+        }
+        let flat: Vec<S> = read_samples(&mut r, header.channels * header.samples_per_channel)?;
+        let buf = match header.layout {
+            IqLayout::Planar => {
+                let samples = header.samples_per_channel;
+                let mut out = unsafe { ArrayBuffer::uninit(header.channels, samples) };
+                for (channel, chunk) in flat.chunks(samples).enumerate() {
+                    out.get_mut(channel).unwrap().clone_from_slice(chunk);
+                }
+                out
+            }
+            IqLayout::Interleaved => {
+                InterleavedBuffer::from_vec(header.channels, flat).deinterleave()
+            }
+        };
+        Ok((buf, header.meta))
+    }
+}
+
+impl<S: Sample> InterleavedBuffer<S> {
+    /// Write this buffer to `path` in the compact UIQF binary format,
+    /// alongside `meta` captured at record time.
+    ///
+    /// Samples are written frame-major (interleaved), matching this
+    /// buffer's own in-memory layout directly.
+    pub fn write_to(&self, path: impl AsRef<Path>, meta: CaptureMeta) -> io::Result<()> {
+        let mut w = BufWriter::new(File::create(path)?);
+        write_header(
+            &mut w,
+            IqLayout::Interleaved,
+            S::name(),
+            self.channels(),
+            self.len(),
+            meta,
+        )?;
+        write_samples(&mut w, self.as_slice())?;
+        w.flush()
+    }
+
+    /// Read a UIQF recording written by [`write_to`](Self::write_to) or
+    /// [`ArrayBuffer::write_to`], returning the decoded buffer (converted
+    /// to interleaved layout if it was recorded planar) alongside the
+    /// [`CaptureMeta`] it was recorded with.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `path` isn't a UIQF recording, or was written for a
+    /// different sample type than `S`.
+    pub fn read_from(path: impl AsRef<Path>) -> io::Result<(Self, CaptureMeta)>
+    where
+        S: Clone,
+    {
+        let (planar, meta) = ArrayBuffer::<S>::read_from(path)?;
+        Ok((planar.interleave(), meta))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::CaptureMeta;
+    use crate::{ArrayBuffer, InterleavedBuffer};
+
+    static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("uiqf_test_{name}_{}_{id}.bin", std::process::id()))
+    }
+
+    #[test]
+    fn test_array_buffer_roundtrip() {
+        let path = temp_path("array_roundtrip");
+        let buf: ArrayBuffer<i16> =
+            ArrayBuffer::from_nested_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        buf.write_to(&path, CaptureMeta::new(1e6, 915e6)).unwrap();
+
+        let (read_back, meta) = ArrayBuffer::<i16>::read_from(&path).unwrap();
+        assert_eq!(read_back, buf);
+        assert_eq!(meta, CaptureMeta::new(1e6, 915e6));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_interleaved_roundtrip_via_planar() {
+        let path = temp_path("interleaved_roundtrip");
+        let buf = InterleavedBuffer::from_vec(2, vec![1i16, 2, 3, 4, 5, 6]);
+        buf.write_to(&path, CaptureMeta::default()).unwrap();
+
+        let (read_back, meta) = InterleavedBuffer::<i16>::read_from(&path).unwrap();
+        assert_eq!(read_back.as_slice(), buf.as_slice());
+        assert_eq!(meta, CaptureMeta::default());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_mismatched_sample_type() {
+        let path = temp_path("mismatch");
+        let buf: ArrayBuffer<i16> = ArrayBuffer::from_nested_vec(vec![vec![1, 2]]);
+        buf.write_to(&path, CaptureMeta::default()).unwrap();
+
+        assert!(ArrayBuffer::<f32>::read_from(&path).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
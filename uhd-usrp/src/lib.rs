@@ -64,20 +64,40 @@
 //! }
 //! ```
 
+pub mod agc;
 mod buffer;
+mod buffer_pool;
 mod error;
 pub(crate) mod ffi;
+pub mod hal;
+mod history_buffer;
+mod iq_file;
 pub mod logging;
 pub(crate) mod misc_types;
+pub mod power_meter;
+pub mod recording;
+pub mod rfnoc;
 mod sample;
 mod time;
+pub mod types;
 pub mod usrp;
 
-pub use buffer::{ArrayBuffer, SampleBuffer};
-pub use error::{last_error_message, Result, UhdError};
+pub use agc::{AgcConfig, SoftwareAgc};
+pub use buffer::{
+    ArrayBuffer, Block, BlockMut, FrameRef, FrameRefMut, Frames, FramesMut, InterleavedBuffer,
+    SampleBuffer, StackBuffer, StaticArrayBuffer,
+};
+pub use buffer_pool::{BufferPool, PooledBuffer};
+pub use error::{last_error_message, Result, TimeError, UhdError};
+pub use hal::{RxSource, TxSink, UsrpDevice};
+pub use history_buffer::HistoryBuffer;
+pub use iq_file::CaptureMeta;
 pub use misc_types::*;
+pub use power_meter::{channel_power_db, PowerMeter, PowerMeterConfig};
+pub use recording::{RecordFormat, RecordSink};
 pub use sample::Sample;
 pub use time::TimeSpec;
+pub use types::DeviceArgs;
 pub use usrp::*;
 
 pub(crate) use crate::error::try_uhd;
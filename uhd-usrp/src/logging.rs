@@ -1,4 +1,10 @@
-use std::path::{Path, PathBuf};
+use std::{
+    ffi::CString,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use crate::ffi::FfiString;
 
 /// Log levels are used to filter messages based on level of severity.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -76,3 +82,123 @@ pub fn set_log_file(path: Option<impl AsRef<Path>>) {
 pub fn log_file() -> Option<PathBuf> {
     std::env::var("UHD_LOG_FILE").ok().map(|p| PathBuf::from(p))
 }
+
+type LogHandlerFn = dyn FnMut(LogLevel, &str, &str) + Send;
+
+static LOG_HANDLER: Mutex<Option<Box<LogHandlerFn>>> = Mutex::new(None);
+
+/// Register a callback to receive UHD's native log messages as they are
+/// emitted, in addition to (not instead of) whatever file/console logging
+/// is configured via [`set_file_log_level`]/[`set_console_log_level`].
+///
+/// `handler` is called with `(level, component, message)` for every
+/// message UHD logs. Replaces any handler registered by an earlier call.
+///
+/// UHD may invoke the handler from one of its own internal threads; a
+/// panic inside `handler` is caught at the FFI boundary and discarded
+/// rather than unwinding into UHD's C code.
+pub fn set_log_handler(handler: impl FnMut(LogLevel, &str, &str) + Send + 'static) {
+    *lock_handler() = Some(Box::new(handler));
+    let logger_name = CString::new("rust").unwrap();
+    unsafe {
+        uhd_usrp_sys::uhd_log_add_logger(logger_name.as_ptr(), Some(log_trampoline));
+    }
+}
+
+/// Remove any log handler previously registered with [`set_log_handler`]
+/// or [`init_log_bridge`].
+pub fn clear_log_handler() {
+    *lock_handler() = None;
+}
+
+/// Lock [`LOG_HANDLER`], tolerating poisoning.
+///
+/// The lock is never held across a call into the handler itself (see
+/// [`log_trampoline`]), so the only way it can become poisoned is a panic
+/// during the brief take/restore around that call — in which case the
+/// handler itself is already gone, and there's nothing left to protect by
+/// keeping the mutex poisoned.
+fn lock_handler() -> std::sync::MutexGuard<'static, Option<Box<LogHandlerFn>>> {
+    LOG_HANDLER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Convenience wrapper around [`set_log_handler`] that forwards UHD's
+/// messages to the [`log`] crate's facade, using the message's component
+/// as the record's target and mapping [`LogLevel`] onto [`log::Level`]
+/// (`Fatal` is reported as [`log::Level::Error`], since `log` has no
+/// more severe level).
+#[cfg(feature = "log")]
+pub fn init_log_bridge() {
+    set_log_handler(|level, component, message| {
+        let level = match level {
+            LogLevel::Trace => log::Level::Trace,
+            LogLevel::Debug => log::Level::Debug,
+            LogLevel::Info => log::Level::Info,
+            LogLevel::Warning => log::Level::Warn,
+            LogLevel::Error | LogLevel::Fatal => log::Level::Error,
+            LogLevel::Off => return,
+        };
+        log::log!(target: component, level, "{message}");
+    });
+}
+
+fn message_severity(handle: uhd_usrp_sys::uhd_log_message_handle) -> Option<LogLevel> {
+    let mut severity = 0u32;
+    unsafe {
+        uhd_usrp_sys::uhd_log_message_severity(handle, std::ptr::addr_of_mut!(severity));
+    }
+    match severity {
+        0 => Some(LogLevel::Trace),
+        1 => Some(LogLevel::Debug),
+        2 => Some(LogLevel::Info),
+        3 => Some(LogLevel::Warning),
+        4 => Some(LogLevel::Error),
+        5 => Some(LogLevel::Fatal),
+        _ => None,
+    }
+}
+
+fn message_component(handle: uhd_usrp_sys::uhd_log_message_handle) -> Option<String> {
+    FfiString::get_with_retry(128, 4096, |s| {
+        crate::try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_log_message_component(handle, s.as_mut_ptr(), s.max_chars())
+        })
+    })
+    .ok()
+}
+
+fn message_text(handle: uhd_usrp_sys::uhd_log_message_handle) -> Option<String> {
+    FfiString::get_with_retry(256, 65536, |s| {
+        crate::try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_log_message_message(handle, s.as_mut_ptr(), s.max_chars())
+        })
+    })
+    .ok()
+}
+
+unsafe extern "C" fn log_trampoline(handle: uhd_usrp_sys::uhd_log_message_handle) {
+    let (Some(severity), Some(component), Some(text)) = (
+        message_severity(handle),
+        message_component(handle),
+        message_text(handle),
+    ) else {
+        return;
+    };
+
+    // Take the handler out from under the lock before calling it, so a
+    // panicking handler can't poison `LOG_HANDLER` while it's held across
+    // the call (and so `clear_log_handler` from inside the handler itself
+    // can't deadlock).
+    let Some(mut handler) = lock_handler().take() else {
+        return;
+    };
+
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        handler(severity, &component, &text);
+    }));
+
+    let mut guard = lock_handler();
+    if guard.is_none() {
+        *guard = Some(handler);
+    }
+}
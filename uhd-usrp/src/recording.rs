@@ -0,0 +1,440 @@
+//! Recording IQ captures to disk.
+//!
+//! [`RecordSink`] consumes blocks coming out of an [`RxStream`](crate::RxStream)
+//! (directly, or via [`RxStream::run`](crate::RxStream::run)) and writes them
+//! to a [SigMF](https://github.com/sigmf/SigMF) container: a `.sigmf-data`
+//! file holding the raw interleaved samples plus a `.sigmf-meta` JSON file
+//! describing the capture, following the `write_start` → write frames →
+//! `finalize` split used by media-file writers. A minimal WAV variant is
+//! also available for tools that expect that container instead.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Seek, SeekFrom, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    buffer::SampleBuffer,
+    usrp::{RxErrorCode, RxMetadata},
+    Sample,
+};
+
+/// The on-disk container a [`RecordSink`] writes to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// A `.sigmf-data`/`.sigmf-meta` pair.
+    SigMf,
+    /// A single `.wav` file.
+    Wav,
+}
+
+struct SigmfCapture {
+    sample_start: u64,
+    frequency: f64,
+    /// Seconds into the stream's own (device/firmware-relative) clock that
+    /// this capture started at, as reported by the first [`RxMetadata`]
+    /// seen. Not a wall-clock time, so it's recorded as a non-`core` key
+    /// rather than `core:datetime`, which SigMF requires to be an ISO-8601
+    /// UTC timestamp.
+    stream_time_secs: Option<f64>,
+}
+
+struct SigmfAnnotation {
+    sample_start: u64,
+    sample_count: u64,
+    comment: &'static str,
+}
+
+/// Writes captured IQ samples from an [`RxStream<T>`](crate::RxStream) to disk.
+///
+/// Create one with [`RecordSink::create`], append received blocks with
+/// [`write_block`](Self::write_block), and call [`finalize`](Self::finalize)
+/// once the capture is done to flush the container's header/metadata.
+pub struct RecordSink<T: Sample> {
+    format: RecordFormat,
+    data: BufWriter<File>,
+    meta_path: PathBuf,
+    center_freq: f64,
+    sample_rate: f64,
+    channels: usize,
+    samples_written: u64,
+    captures: Vec<SigmfCapture>,
+    annotations: Vec<SigmfAnnotation>,
+    scratch: Vec<T>,
+    _sample: PhantomData<T>,
+}
+
+impl<T: Sample> RecordSink<T> {
+    /// Creates a SigMF recording rooted at `path` (without an extension):
+    /// `<path>.sigmf-data` and `<path>.sigmf-meta` are created alongside
+    /// each other, per the SigMF convention of sharing a basename.
+    ///
+    /// `sample_rate` (in Hz) is recorded in the SigMF `global` segment and
+    /// `center_freq` (in Hz) in the first `captures` entry; for
+    /// [`RecordFormat::Wav`], `sample_rate` sets the WAV header's sample rate.
+    pub fn create(
+        path: impl AsRef<Path>,
+        center_freq: f64,
+        sample_rate: f64,
+        channels: usize,
+    ) -> io::Result<Self> {
+        Self::create_with_format(path, center_freq, sample_rate, channels, RecordFormat::SigMf)
+    }
+
+    /// The center frequency this recording was created with, in Hz.
+    pub fn center_freq(&self) -> f64 {
+        self.center_freq
+    }
+
+    /// The sample rate this recording was created with, in Hz.
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Like [`create`](Self::create), but lets the container format be
+    /// chosen explicitly instead of defaulting to SigMF.
+    pub fn create_with_format(
+        path: impl AsRef<Path>,
+        center_freq: f64,
+        sample_rate: f64,
+        channels: usize,
+        format: RecordFormat,
+    ) -> io::Result<Self> {
+        let path = path.as_ref();
+        let (data_path, meta_path) = match format {
+            RecordFormat::SigMf => (
+                path.with_extension("sigmf-data"),
+                path.with_extension("sigmf-meta"),
+            ),
+            RecordFormat::Wav => (path.with_extension("wav"), PathBuf::new()),
+        };
+        let mut data = BufWriter::new(File::create(data_path)?);
+        if let RecordFormat::Wav = format {
+            write_wav_placeholder_header(&mut data, channels, sample_rate)?;
+        }
+
+        Ok(Self {
+            format,
+            data,
+            meta_path,
+            center_freq,
+            sample_rate,
+            channels,
+            samples_written: 0,
+            captures: vec![SigmfCapture {
+                sample_start: 0,
+                frequency: center_freq,
+                stream_time_secs: None,
+            }],
+            annotations: Vec::new(),
+            scratch: Vec::new(),
+            _sample: PhantomData,
+        })
+    }
+
+    /// Appends one block received from an `RxStream<T>` — `buff` holding
+    /// `samples` samples per channel, channel-major as UHD delivers it —
+    /// interleaving it into frame-major order (`c0s0, c1s0, c0s1, ...`) on
+    /// the way to disk, and folds capture-time annotations out of `md`
+    /// (the first timestamp seen, and an overflow marker on
+    /// [`RxErrorCode::Overflow`]).
+    pub fn write_block<B>(&mut self, buff: &B, samples: usize, md: &RxMetadata) -> io::Result<()>
+    where
+        B: SampleBuffer<T>,
+        T: Copy,
+    {
+        assert!(buff.channels() >= self.channels);
+
+        self.scratch.clear();
+        self.scratch.reserve(samples * self.channels);
+        interleave_block(buff, samples, self.channels, &mut self.scratch);
+
+        if self.captures[0].stream_time_secs.is_none() {
+            if let Some(ts) = md.time_spec() {
+                self.captures[0].stream_time_secs = Some(ts.full_secs() as f64 + ts.frac_secs());
+            }
+        }
+        if let Ok(RxErrorCode::Overflow) = md.error_code() {
+            self.annotations.push(SigmfAnnotation {
+                sample_start: self.samples_written,
+                sample_count: samples as u64,
+                comment: "overflow",
+            });
+        }
+
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                self.scratch.as_ptr().cast::<u8>(),
+                std::mem::size_of_val(self.scratch.as_slice()),
+            )
+        };
+        self.data.write_all(bytes)?;
+        self.samples_written += samples as u64;
+        Ok(())
+    }
+
+    /// Flushes the data file and, for [`RecordFormat::SigMf`], writes the
+    /// `.sigmf-meta` header; for [`RecordFormat::Wav`], patches the RIFF/
+    /// data chunk sizes now that the final length is known.
+    pub fn finalize(mut self) -> io::Result<()> {
+        self.data.flush()?;
+        match self.format {
+            RecordFormat::SigMf => self.write_sigmf_meta(),
+            RecordFormat::Wav => {
+                let mut file = self.data.into_inner().map_err(|e| e.into_error())?;
+                patch_wav_header(
+                    &mut file,
+                    self.samples_written,
+                    self.channels,
+                    self.sample_rate,
+                    T::name(),
+                )
+            }
+        }
+    }
+
+    fn write_sigmf_meta(&self) -> io::Result<()> {
+        let captures = self
+            .captures
+            .iter()
+            .map(|c| {
+                // Not `core:datetime`: SigMF requires that to be an
+                // ISO-8601 UTC timestamp, but `stream_time_secs` is a
+                // device/stream-relative time with no epoch to anchor it.
+                let stream_time = c
+                    .stream_time_secs
+                    .map(|secs| format!(r#","rust_uhd:stream_time_secs":{secs:.9}"#))
+                    .unwrap_or_default();
+                format!(
+                    r#"{{"core:sample_start":{},"core:frequency":{}{stream_time}}}"#,
+                    c.sample_start, c.frequency
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let annotations = self
+            .annotations
+            .iter()
+            .map(|a| {
+                format!(
+                    r#"{{"core:sample_start":{},"core:sample_count":{},"core:comment":"{}"}}"#,
+                    a.sample_start, a.sample_count, a.comment
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let global = format!(
+            r#"{{"core:datatype":"{}","core:sample_rate":{},"core:num_channels":{},"core:version":"1.0.0"}}"#,
+            sigmf_datatype::<T>(),
+            self.sample_rate,
+            self.channels,
+        );
+        let meta = format!(
+            r#"{{"global":{global},"captures":[{captures}],"annotations":[{annotations}]}}"#
+        );
+        std::fs::write(&self.meta_path, meta)
+    }
+}
+
+/// Copies `samples` samples per channel out of `buff`'s channel-major
+/// layout (as UHD delivers it) into `out`, appended in frame-major order
+/// (`c0s0, c1s0, …, c0s1, c1s1, …`).
+fn interleave_block<T: Sample + Copy>(
+    buff: &impl SampleBuffer<T>,
+    samples: usize,
+    channels: usize,
+    out: &mut Vec<T>,
+) {
+    let ptrs = buff.as_ptr();
+    for i in 0..samples {
+        for c in 0..channels {
+            // Safety: `ptrs` is the channel-pointer table UHD itself just
+            // wrote into via `recv`, and `i < samples <= buff.samples()`.
+            let value = unsafe { *(*ptrs.add(c)).add(i) };
+            out.push(value);
+        }
+    }
+}
+
+/// Maps a [`Sample`] to its SigMF `core:datatype` token.
+///
+/// SigMF's complex tokens are little-endian by convention on every
+/// platform this crate targets, so the mapping is fixed rather than
+/// consulting target-endianness.
+fn sigmf_datatype<T: Sample>() -> &'static str {
+    match T::name() {
+        "fc32" => "cf32_le",
+        "fc64" => "cf64_le",
+        "sc16" => "ci16_le",
+        "sc8" => "ci8",
+        "s16" => "ri16_le",
+        "s8" => "ri8",
+        other => other,
+    }
+}
+
+fn write_wav_placeholder_header(
+    w: &mut impl Write,
+    channels: usize,
+    sample_rate: f64,
+) -> io::Result<()> {
+    // Placeholder sizes; patched by `patch_wav_header` once the final
+    // sample count (and hence `T`'s byte width) is known.
+    w.write_all(b"RIFF")?;
+    w.write_all(&0u32.to_le_bytes())?;
+    w.write_all(b"WAVE")?;
+    w.write_all(b"fmt ")?;
+    w.write_all(&16u32.to_le_bytes())?;
+    w.write_all(&1u16.to_le_bytes())?; // format tag patched later
+    w.write_all(&(channels as u16).to_le_bytes())?;
+    w.write_all(&(sample_rate as u32).to_le_bytes())?;
+    w.write_all(&0u32.to_le_bytes())?; // byte rate, patched later
+    w.write_all(&0u16.to_le_bytes())?; // block align, patched later
+    w.write_all(&0u16.to_le_bytes())?; // bits per sample, patched later
+    w.write_all(b"data")?;
+    w.write_all(&0u32.to_le_bytes())?;
+    Ok(())
+}
+
+fn patch_wav_header(
+    file: &mut File,
+    samples_written: u64,
+    channels: usize,
+    sample_rate: f64,
+    sample_format: &'static str,
+) -> io::Result<()> {
+    let is_float = matches!(sample_format, "fc32" | "fc64");
+    let components_per_sample = match sample_format {
+        "s16" | "s8" => 1,
+        _ => 2, // complex formats interleave real/imag
+    };
+    let bits_per_sample = match sample_format {
+        "fc32" => 32,
+        "fc64" => 64,
+        "sc16" => 16,
+        "sc8" => 8,
+        "s16" => 16,
+        "s8" => 8,
+        _ => 32,
+    };
+    let bytes_per_component = bits_per_sample / 8;
+    let block_align = channels * components_per_sample * bytes_per_component;
+    let data_bytes = samples_written * block_align as u64;
+    let byte_rate = sample_rate as u32 * block_align as u32;
+
+    file.seek(SeekFrom::Start(4))?;
+    file.write_all(&(36u32 + data_bytes as u32).to_le_bytes())?;
+    file.seek(SeekFrom::Start(20))?;
+    file.write_all(&(if is_float { 3u16 } else { 1u16 }).to_le_bytes())?;
+    file.seek(SeekFrom::Start(28))?;
+    file.write_all(&byte_rate.to_le_bytes())?;
+    file.seek(SeekFrom::Start(32))?;
+    file.write_all(&(block_align as u16).to_le_bytes())?;
+    file.write_all(&(bits_per_sample as u16).to_le_bytes())?;
+    file.seek(SeekFrom::Start(40))?;
+    file.write_all(&(data_bytes as u32).to_le_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Seek, SeekFrom},
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
+    use super::{interleave_block, patch_wav_header, sigmf_datatype, write_wav_placeholder_header};
+    use crate::ArrayBuffer;
+
+    static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("recording_test_{name}_{}_{id}.bin", std::process::id()))
+    }
+
+    #[test]
+    fn test_sigmf_datatype_mapping() {
+        assert_eq!(sigmf_datatype::<i16>(), "ri16_le");
+        assert_eq!(sigmf_datatype::<i8>(), "ri8");
+        assert_eq!(sigmf_datatype::<[f32; 2]>(), "cf32_le");
+        assert_eq!(sigmf_datatype::<[f64; 2]>(), "cf64_le");
+    }
+
+    #[test]
+    fn test_interleave_block() {
+        let buff: ArrayBuffer<i16> =
+            ArrayBuffer::from_nested_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let mut out = Vec::new();
+        interleave_block(&buff, 3, 2, &mut out);
+        assert_eq!(out, vec![1, 4, 2, 5, 3, 6]);
+    }
+
+    #[test]
+    fn test_interleave_block_partial() {
+        let buff: ArrayBuffer<i16> =
+            ArrayBuffer::from_nested_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let mut out = Vec::new();
+        interleave_block(&buff, 2, 2, &mut out);
+        assert_eq!(out, vec![1, 4, 2, 5]);
+    }
+
+    #[test]
+    fn test_patch_wav_header() {
+        let path = temp_path("patch_wav_header");
+        {
+            let mut file = std::fs::File::create(&path).unwrap();
+            write_wav_placeholder_header(&mut file, 2, 48_000.0).unwrap();
+            patch_wav_header(&mut file, 10, 2, 48_000.0, "s16").unwrap();
+        }
+
+        let mut file = std::fs::File::open(&path).unwrap();
+        let mut header = [0u8; 44];
+        file.read_exact(&mut header).unwrap();
+
+        let riff_size = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        let format_tag = u16::from_le_bytes(header[20..22].try_into().unwrap());
+        let byte_rate = u32::from_le_bytes(header[28..32].try_into().unwrap());
+        let block_align = u16::from_le_bytes(header[32..34].try_into().unwrap());
+        let bits_per_sample = u16::from_le_bytes(header[34..36].try_into().unwrap());
+        let data_size = u32::from_le_bytes(header[40..44].try_into().unwrap());
+
+        // 2 channels * 16-bit samples = 4 bytes/frame, 10 frames = 40 bytes of data.
+        assert_eq!(format_tag, 1); // PCM, not IEEE float
+        assert_eq!(block_align, 4);
+        assert_eq!(bits_per_sample, 16);
+        assert_eq!(data_size, 40);
+        assert_eq!(riff_size, 36 + 40);
+        assert_eq!(byte_rate, 48_000 * 4);
+
+        drop(file.seek(SeekFrom::Start(0)));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_patch_wav_header_float() {
+        let path = temp_path("patch_wav_header_float");
+        {
+            let mut file = std::fs::File::create(&path).unwrap();
+            write_wav_placeholder_header(&mut file, 1, 1e6).unwrap();
+            patch_wav_header(&mut file, 4, 1, 1e6, "fc32").unwrap();
+        }
+
+        let mut file = std::fs::File::open(&path).unwrap();
+        let mut header = [0u8; 44];
+        file.read_exact(&mut header).unwrap();
+
+        let format_tag = u16::from_le_bytes(header[20..22].try_into().unwrap());
+        let block_align = u16::from_le_bytes(header[32..34].try_into().unwrap());
+        let bits_per_sample = u16::from_le_bytes(header[34..36].try_into().unwrap());
+
+        // `fc32` is complex (real + imaginary), 32 bits each.
+        assert_eq!(format_tag, 3); // IEEE float
+        assert_eq!(block_align, 8);
+        assert_eq!(bits_per_sample, 32);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
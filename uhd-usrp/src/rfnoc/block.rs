@@ -0,0 +1,73 @@
+use std::ffi::CString;
+use std::ptr::addr_of_mut;
+
+use crate::{ffi::FfiString, try_uhd, Result, UhdError};
+
+use super::RfnocGraph;
+
+/// A handle to a single block controller within an [`RfnocGraph`].
+///
+/// Block controllers are looked up by block ID (e.g. `"0/DDC#0"`) via
+/// [`RfnocGraph::block`].
+pub struct RfnocBlock {
+    block_id: String,
+}
+
+impl RfnocBlock {
+    pub(crate) fn new(graph: &RfnocGraph, block_id: &str) -> Result<Self> {
+        let id = CString::new(block_id).unwrap();
+        let mut has_block = false;
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rfnoc_graph_has_block(
+                graph.handle().as_mut_ptr(),
+                id.as_ptr(),
+                addr_of_mut!(has_block),
+            )
+        })?;
+        if !has_block {
+            return Err(UhdError::Key);
+        }
+        Ok(Self {
+            block_id: block_id.to_string(),
+        })
+    }
+
+    /// The block ID this handle refers to, e.g. `"0/DDC#0"`.
+    pub fn block_id(&self) -> &str {
+        &self.block_id
+    }
+
+    /// Get the value of a named property on this block.
+    pub fn property(&self, graph: &RfnocGraph, name: &str) -> Result<String> {
+        let block_id = CString::new(self.block_id.as_str()).unwrap();
+        let name = CString::new(name).unwrap();
+        let mut value = FfiString::with_capacity(128);
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rfnoc_graph_get_property(
+                graph.handle().as_mut_ptr(),
+                name.as_ptr(),
+                0,
+                block_id.as_ptr(),
+                value.as_mut_ptr(),
+                value.max_chars(),
+            )
+        })?;
+        value.into_string()
+    }
+
+    /// Set the value of a named property on this block.
+    pub fn set_property(&self, graph: &RfnocGraph, name: &str, value: &str) -> Result<()> {
+        let block_id = CString::new(self.block_id.as_str()).unwrap();
+        let name = CString::new(name).unwrap();
+        let value = CString::new(value).unwrap();
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rfnoc_graph_set_property(
+                graph.handle().as_mut_ptr(),
+                name.as_ptr(),
+                value.as_ptr(),
+                0,
+                block_id.as_ptr(),
+            )
+        })
+    }
+}
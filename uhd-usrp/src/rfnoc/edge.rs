@@ -0,0 +1,28 @@
+/// A single port of a block within an RFNoC graph.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GraphPort {
+    pub block_id: String,
+    pub port: usize,
+}
+
+impl GraphPort {
+    pub fn new(block_id: impl Into<String>, port: usize) -> Self {
+        Self {
+            block_id: block_id.into(),
+            port,
+        }
+    }
+}
+
+/// A connection between two block ports in an RFNoC graph.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GraphEdge {
+    pub src: GraphPort,
+    pub dst: GraphPort,
+}
+
+impl GraphEdge {
+    pub fn new(src: GraphPort, dst: GraphPort) -> Self {
+        Self { src, dst }
+    }
+}
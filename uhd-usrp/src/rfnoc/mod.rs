@@ -0,0 +1,231 @@
+//! RFNoC (RF Network-on-Chip) graph support.
+//!
+//! This module provides block-level control over an RFNoC-capable USRP,
+//! as an alternative to the classic [`Usrp`](crate::Usrp) streaming API.
+//! An [`RfnocGraph`] represents the full flowgraph of blocks available on
+//! one or more connected devices; individual blocks are accessed through
+//! [`RfnocBlock`] handles obtained by block ID.
+
+mod block;
+mod edge;
+
+pub use block::RfnocBlock;
+pub use edge::{GraphEdge, GraphPort};
+
+use std::{ffi::CString, ptr::addr_of_mut};
+
+use crate::{
+    ffi::{FfiString, FfiStringVec, OwnedHandle},
+    try_uhd,
+    usrp::stream::{RxStream, TxStream},
+    Result, Sample, UhdError,
+};
+
+/// A handle to an RFNoC graph.
+///
+/// The graph owns every block controller and static connection on the
+/// device(s) it was created from. Connections must be made with
+/// [`RfnocGraph::connect`] before the graph is [committed](RfnocGraph::commit);
+/// streamers can only be created afterwards.
+///
+/// # Examples
+///
+/// ```no_run
+/// use uhd_usrp::rfnoc::RfnocGraph;
+///
+/// let mut graph = RfnocGraph::open_with_args("addr=192.168.10.4").expect("failed to open graph");
+/// graph
+///     .connect("0/Radio#0", "0/DDC#0")
+///     .expect("failed to connect radio to ddc");
+/// graph.commit().expect("failed to commit graph");
+/// ```
+pub struct RfnocGraph {
+    handle: OwnedHandle<uhd_usrp_sys::uhd_rfnoc_graph>,
+    committed: bool,
+}
+
+impl RfnocGraph {
+    /// Open an RFNoC graph using `"key=value"`-style device arguments.
+    pub fn open_with_args(args: &str) -> Result<Self> {
+        let mut handle = std::ptr::null_mut();
+        let args = CString::new(args).unwrap();
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rfnoc_graph_make(addr_of_mut!(handle), args.as_ptr())
+        })?;
+        Ok(Self {
+            handle: unsafe { OwnedHandle::from_ptr(handle, uhd_usrp_sys::uhd_rfnoc_graph_free) },
+            committed: false,
+        })
+    }
+
+    /// Get a reference to the underlying [`OwnedHandle`].
+    pub(crate) fn handle(&self) -> &OwnedHandle<uhd_usrp_sys::uhd_rfnoc_graph> {
+        &self.handle
+    }
+
+    /// Returns `true` once [`commit`](Self::commit) has been called successfully.
+    pub fn is_committed(&self) -> bool {
+        self.committed
+    }
+
+    /// List the block IDs present in this graph, e.g. `"0/Radio#0"`.
+    pub fn block_ids(&self) -> Result<Vec<String>> {
+        let mut vec = FfiStringVec::new();
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rfnoc_graph_get_block_ids(self.handle.as_mut_ptr(), vec.as_mut_ptr())
+        })?;
+        Ok(vec.to_vec())
+    }
+
+    /// Get a handle to a block controller by its block ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UhdError::Key`] if no block with the given ID exists in
+    /// this graph.
+    pub fn block(&self, block_id: &str) -> Result<RfnocBlock> {
+        RfnocBlock::new(self, block_id)
+    }
+
+    /// Create a static or dynamic connection between two block ports.
+    ///
+    /// `src_block` and `dst_block` are block IDs as returned by
+    /// [`block_ids`](Self::block_ids); `src_port`/`dst_port` default to `0`.
+    /// Use [`connect_ports`](Self::connect_ports) to specify non-zero ports.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UhdError::Runtime`] if the graph has already been
+    /// [committed](Self::commit) -- connections cannot be added afterwards.
+    pub fn connect(&mut self, src_block: &str, dst_block: &str) -> Result<()> {
+        self.connect_ports(src_block, 0, dst_block, 0)
+    }
+
+    /// Create a connection between specific ports of two blocks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UhdError::Runtime`] if the graph has already been
+    /// [committed](Self::commit) -- connections cannot be added afterwards.
+    pub fn connect_ports(
+        &mut self,
+        src_block: &str,
+        src_port: usize,
+        dst_block: &str,
+        dst_port: usize,
+    ) -> Result<()> {
+        if self.committed {
+            return Err(UhdError::Runtime);
+        }
+        let src_block = CString::new(src_block).unwrap();
+        let dst_block = CString::new(dst_block).unwrap();
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rfnoc_graph_connect(
+                self.handle.as_mut_ptr(),
+                src_block.as_ptr(),
+                src_port,
+                dst_block.as_ptr(),
+                dst_port,
+                false,
+            )
+        })
+    }
+
+    /// Commit the graph, finalizing all connections made with
+    /// [`connect`](Self::connect).
+    ///
+    /// This must be called before any RX/TX streamer can be created. Once
+    /// committed, no further connections can be added.
+    pub fn commit(&mut self) -> Result<()> {
+        try_uhd!(unsafe { uhd_usrp_sys::uhd_rfnoc_graph_commit(self.handle.as_mut_ptr()) })?;
+        self.committed = true;
+        Ok(())
+    }
+
+    /// Read a value from the graph's property tree.
+    pub fn property(&self, path: &str, block_id: &str) -> Result<String> {
+        let path = CString::new(path).unwrap();
+        let block_id = CString::new(block_id).unwrap();
+        let mut value = FfiString::with_capacity(128);
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rfnoc_graph_get_property(
+                self.handle.as_mut_ptr(),
+                path.as_ptr(),
+                0,
+                block_id.as_ptr(),
+                value.as_mut_ptr(),
+                value.max_chars(),
+            )
+        })?;
+        value.into_string()
+    }
+
+    /// Create an RX streamer bound to the given block/port, with `T` as the
+    /// host-side sample type.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UhdError::Runtime`] if the graph has not yet been
+    /// [committed](Self::commit).
+    pub fn create_rx_streamer<T: Sample>(
+        &self,
+        block_id: &str,
+        port: usize,
+    ) -> Result<RxStream<T>> {
+        if !self.committed {
+            return Err(UhdError::Runtime);
+        }
+        let block_id = CString::new(block_id).unwrap();
+        let mut handle: uhd_usrp_sys::uhd_rx_streamer_handle = std::ptr::null_mut();
+        if let Err(e) = try_uhd!(unsafe { uhd_usrp_sys::uhd_rx_streamer_make(&mut handle) }) {
+            unsafe { uhd_usrp_sys::uhd_rx_streamer_free(addr_of_mut!(handle)) };
+            return Err(e);
+        }
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rfnoc_graph_create_rx_streamer(
+                self.handle.as_mut_ptr(),
+                block_id.as_ptr(),
+                port,
+                handle,
+            )
+        })?;
+        RxStream::<T>::new(
+            unsafe { OwnedHandle::from_ptr(handle, uhd_usrp_sys::uhd_rx_streamer_free) },
+            None,
+        )
+    }
+
+    /// Create a TX streamer bound to the given block/port, with `T` as the
+    /// host-side sample type.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UhdError::Runtime`] if the graph has not yet been
+    /// [committed](Self::commit).
+    pub fn create_tx_streamer<T: Sample>(
+        &self,
+        block_id: &str,
+        port: usize,
+    ) -> Result<TxStream<T>> {
+        if !self.committed {
+            return Err(UhdError::Runtime);
+        }
+        let block_id = CString::new(block_id).unwrap();
+        let mut handle: uhd_usrp_sys::uhd_tx_streamer_handle = std::ptr::null_mut();
+        if let Err(e) = try_uhd!(unsafe { uhd_usrp_sys::uhd_tx_streamer_make(&mut handle) }) {
+            unsafe { uhd_usrp_sys::uhd_tx_streamer_free(addr_of_mut!(handle)) };
+            return Err(e);
+        }
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rfnoc_graph_create_tx_streamer(
+                self.handle.as_mut_ptr(),
+                block_id.as_ptr(),
+                port,
+                handle,
+            )
+        })?;
+        TxStream::<T>::new(unsafe {
+            OwnedHandle::from_ptr(handle, uhd_usrp_sys::uhd_tx_streamer_free)
+        })
+    }
+}
@@ -46,6 +46,9 @@ macro_rules! timespec {
     (0) => {{
         TimeSpec::ZERO
     }};
+    ($val:literal h) => {{
+        TimeSpec::from_secs_f64($val as f64 * 3600.0)
+    }};
     ($val:literal m) => {{
         TimeSpec::from_secs_f64($val as f64 * 60.0)
     }};
@@ -61,6 +64,9 @@ macro_rules! timespec {
     ($val:literal ns) => {{
         TimeSpec::from_secs_f64($val as f64 / 1e9)
     }};
+    ($val:ident h) => {{
+        TimeSpec::from_secs_f64($val as f64 * 3600.0)
+    }};
     ($val:ident m) => {{
         TimeSpec::from_secs_f64($val as f64 * 60.0)
     }};
@@ -78,24 +84,34 @@ macro_rules! timespec {
     }};
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+/// The number of femtoseconds in one second, i.e. the resolution of
+/// [`TimeSpec`]'s fractional part.
+const FEMTOS_PER_SEC: i64 = 1_000_000_000_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimeSpec {
     /// The number of full seconds.
     ///
     /// For negative timespecs the number of full seconds may be one less
-    /// than expected. Note that `full_secs + frac_secs` will still yield
+    /// than expected. Note that `full_secs + frac_femtos` will still yield
     /// the expected value.
     full_secs: i64,
-    /// The number of fractional seconds. For valid timespecs this is always
-    /// in the range `[0, 1)`.
-    frac_secs: f64,
+    /// The number of fractional femtoseconds (10^-15 s). For valid
+    /// timespecs this is always in the range `[0, 1_000_000_000_000_000)`.
+    ///
+    /// Femtoseconds are stored as an exact integer rather than a fraction
+    /// of a second as an `f64`, so that two equal times always compare
+    /// equal, `TimeSpec` can be hashed and totally ordered, and repeated
+    /// arithmetic doesn't accumulate floating-point error.
+    frac_femtos: i64,
 }
 
 impl TimeSpec {
     /// A special value that signifies immediate execution.
-    pub const ZERO: TimeSpec = TimeSpec::from_parts_unchecked(0, 0.0);
-    pub const MAX: TimeSpec = TimeSpec::from_parts_unchecked(i64::MAX, 1.0 - f64::EPSILON);
-    pub const MIN: TimeSpec = TimeSpec::from_parts_unchecked(i64::MIN, 0.0);
+    pub const ZERO: TimeSpec = TimeSpec::from_parts_unchecked(0, 0);
+    pub const MAX: TimeSpec = TimeSpec::from_parts_unchecked(i64::MAX, FEMTOS_PER_SEC - 1);
+    pub const MIN: TimeSpec = TimeSpec::from_parts_unchecked(i64::MIN, 0);
 
     /// Create a new TimeSpec using the number of full and fractional seconds.
     ///
@@ -115,14 +131,31 @@ impl TimeSpec {
             .expect("the given time cannot be represented without overflow")
     }
 
+    /// Like [`from_parts`](Self::from_parts), but requires `frac_secs` to
+    /// already be within `[0, 1)` instead of silently folding any whole
+    /// seconds it contains into `full_secs`.
+    ///
+    /// Useful when building a TimeSpec from a value that's supposed to
+    /// already be normalized (e.g. a hardware tick counter split into
+    /// seconds and a sub-second remainder), where a fractional part
+    /// outside this range indicates a programming bug rather than
+    /// something to silently correct.
+    pub fn from_parts_strict(full_secs: i64, frac_secs: f64) -> Result<Self, TimeError> {
+        if !(0.0..1.0).contains(&frac_secs) {
+            return Err(TimeError::FracOutOfRange);
+        }
+        Self::try_from_parts(full_secs, frac_secs).ok_or(TimeError::Overflow)
+    }
+
     /// Create a new TimeSpec without checking for overflow or normalizing the values.
     ///
-    /// Care should be taken to ensure `frac_secs` is in the range `[0, 1)` and is not
-    /// NaN or infinite. Using invalid values may lead to unexpected results.
-    pub const fn from_parts_unchecked(full_secs: i64, frac_secs: f64) -> Self {
+    /// Care should be taken to ensure `frac_femtos` is in the range
+    /// `[0, 1_000_000_000_000_000)`. Using an out-of-range value may lead
+    /// to unexpected results.
+    pub const fn from_parts_unchecked(full_secs: i64, frac_femtos: i64) -> Self {
         Self {
             full_secs,
-            frac_secs,
+            frac_femtos,
         }
     }
 
@@ -147,19 +180,58 @@ impl TimeSpec {
             frac_secs += 1.0;
             full_secs = full_secs.checked_sub(1)?;
         }
+        let mut frac_femtos = (frac_secs * FEMTOS_PER_SEC as f64).round() as i64;
+        if frac_femtos >= FEMTOS_PER_SEC {
+            // Rounding pushed the fraction up to a whole second.
+            full_secs = full_secs.checked_add(1)?;
+            frac_femtos = 0;
+        }
         Some(Self {
             full_secs,
-            frac_secs,
+            frac_femtos,
         })
     }
 
+    /// Convert a tick count at `tick_rate` ticks/second to a TimeSpec.
+    ///
+    /// The conversion is done in exact integer arithmetic on femtoseconds
+    /// (splitting into full seconds and a fractional femtosecond remainder
+    /// via [`i128::div_euclid`]/[`i128::rem_euclid`]), so repeated
+    /// round-trips through [`to_ticks`](Self::to_ticks) don't accumulate
+    /// error beyond the single unavoidable float multiply needed to scale
+    /// by `tick_rate`.
     pub fn from_ticks(ticks: i64, tick_rate: f64) -> Self {
-        let rate_i = tick_rate as i64;
-        let rate_f = tick_rate - rate_i as f64;
-        let secs_full = ticks / rate_i;
-        let ticks_error = ticks - secs_full * rate_i;
-        let ticks_frac = ticks_error as f64 - secs_full as f64 * rate_f;
-        Self::from_parts(secs_full, ticks_frac / tick_rate)
+        let total_femtos = (ticks as f64 / tick_rate * FEMTOS_PER_SEC as f64).round() as i128;
+        let full_secs = total_femtos.div_euclid(FEMTOS_PER_SEC as i128) as i64;
+        let frac_femtos = total_femtos.rem_euclid(FEMTOS_PER_SEC as i128) as i64;
+        Self {
+            full_secs,
+            frac_femtos,
+        }
+    }
+
+    /// Create a TimeSpec from a number of whole hours.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the equivalent number of seconds overflows an `i64`.
+    pub fn from_hours(hours: i64) -> Self {
+        let secs = hours
+            .checked_mul(3600)
+            .expect("the given number of hours cannot be represented without overflow");
+        Self::from_secs(secs)
+    }
+
+    /// Create a TimeSpec from a number of whole minutes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the equivalent number of seconds overflows an `i64`.
+    pub fn from_minutes(minutes: i64) -> Self {
+        let secs = minutes
+            .checked_mul(60)
+            .expect("the given number of minutes cannot be represented without overflow");
+        Self::from_secs(secs)
     }
 
     pub fn from_secs(secs: i64) -> Self {
@@ -206,8 +278,22 @@ impl TimeSpec {
     ///
     /// Note that for negative TimeSpecs the value may not be as expected.
     /// For example, `-0.3 s` is represnted as `full_secs = -1`, `frac_secs = 0.7`.
-    pub const fn frac_secs(&self) -> f64 {
-        self.frac_secs
+    pub fn frac_secs(&self) -> f64 {
+        self.frac_femtos as f64 / FEMTOS_PER_SEC as f64
+    }
+
+    /// Get the number of fractional femtoseconds (10^-15 s) in the
+    /// TimeSpec.
+    ///
+    /// This is the TimeSpec's exact internal representation of its
+    /// fractional part, and will always be in the range
+    /// `[0, 1_000_000_000_000_000)`.
+    ///
+    /// Note that for negative TimeSpecs the value may not be as expected.
+    /// For example, `-0.3 s` is represented as `full_secs = -1`,
+    /// `frac_femtos = 700_000_000_000_000`.
+    pub const fn frac_femtos(&self) -> i64 {
+        self.frac_femtos
     }
 }
 
@@ -217,7 +303,56 @@ impl TimeSpec {
     ///
     /// For large times the result may result in lowered precision.
     pub fn as_secs(&self) -> f64 {
-        self.full_secs as f64 + self.frac_secs
+        self.full_secs as f64 + self.frac_secs()
+    }
+
+    /// The total femtosecond count of this TimeSpec, combining `full_secs`
+    /// and `frac_femtos` into a single exact integer.
+    fn total_femtos(&self) -> i128 {
+        self.full_secs as i128 * FEMTOS_PER_SEC as i128 + self.frac_femtos as i128
+    }
+
+    /// Convert the TimeSpec to the total number of whole milliseconds it
+    /// represents, truncating any remainder.
+    pub fn as_millis(&self) -> i64 {
+        (self.total_femtos() / (FEMTOS_PER_SEC as i128 / 1_000)) as i64
+    }
+
+    /// Convert the TimeSpec to the total number of whole microseconds it
+    /// represents, truncating any remainder.
+    pub fn as_micros(&self) -> i64 {
+        (self.total_femtos() / (FEMTOS_PER_SEC as i128 / 1_000_000)) as i64
+    }
+
+    /// Convert the TimeSpec to the total number of whole nanoseconds it
+    /// represents, truncating any remainder.
+    pub fn as_nanos(&self) -> i64 {
+        (self.total_femtos() / (FEMTOS_PER_SEC as i128 / 1_000_000_000)) as i64
+    }
+
+    /// Render this TimeSpec as a human-readable `[-]H:MM:SS.fffffffff`
+    /// wall-clock-style string, to nanosecond resolution with trailing
+    /// zeros trimmed.
+    ///
+    /// This is meant for logs and CLIs; the `Display` impl instead prints
+    /// the raw `as_secs()` value.
+    pub fn format_hms(&self) -> String {
+        let abs = self.abs();
+        let total_secs = abs.full_secs() as u64;
+        let hours = total_secs / 3600;
+        let minutes = (total_secs / 60) % 60;
+        let seconds = total_secs % 60;
+        let nanos = (abs.as_nanos() as u64) % 1_000_000_000;
+
+        let mut frac = format!("{nanos:09}");
+        while frac.len() > 1 && frac.ends_with('0') {
+            frac.pop();
+        }
+
+        format!(
+            "{sign}{hours}:{minutes:02}:{seconds:02}.{frac}",
+            sign = if self.is_negative() { "-" } else { "" },
+        )
     }
 
     /// Convert the TimeSpec to a [`Duration`].
@@ -228,19 +363,19 @@ impl TimeSpec {
             None
         } else {
             let secs = u64::try_from(self.full_secs).ok()?;
-            let nanos = (self.frac_secs as f64 * 1_000_000_000.0) as u32;
+            let nanos = (self.frac_secs() * 1_000_000_000.0) as u32;
             Some(Duration::new(secs, nanos))
         }
     }
 
     /// Convert to clock ticks.
+    ///
+    /// The TimeSpec's exact `(full_secs, frac_femtos)` representation is
+    /// combined into a single integer femtosecond count before scaling by
+    /// `tick_rate`, avoiding the precision loss that summing a
+    /// separately-scaled integer and fractional part would have.
     pub fn to_ticks(&self, tick_rate: f64) -> i64 {
-        let rate_i = tick_rate as i64;
-        let rate_f = tick_rate - rate_i as f64;
-        let ticks_full = self.full_secs * rate_i;
-        let ticks_error = self.full_secs as f64 * rate_f;
-        let ticks_frac = self.frac_secs * tick_rate;
-        ticks_full + (ticks_error + ticks_frac).round() as i64
+        (self.total_femtos() as f64 / FEMTOS_PER_SEC as f64 * tick_rate).round() as i64
     }
 }
 
@@ -253,7 +388,7 @@ impl TimeSpec {
 
     /// Check if the time represented by the TimeSpec is exactly zero.
     pub fn is_zero(self) -> bool {
-        self.full_secs == 0 && self.frac_secs == 0.0
+        self.full_secs == 0 && self.frac_femtos == 0
     }
 
     /// Get the sign of the time represented by the TimeSpec.
@@ -262,7 +397,7 @@ impl TimeSpec {
     pub fn sign(self) -> i64 {
         if self.full_secs == 0 {
             // either +1 or 0
-            self.frac_secs.signum() as i64
+            self.frac_femtos.signum()
         } else {
             // either -1 or +1
             self.full_secs.signum()
@@ -285,7 +420,7 @@ impl TimeSpec {
     #[must_use]
     pub fn checked_add(self, rhs: TimeSpec) -> Option<Self> {
         let full_secs = self.full_secs.checked_add(rhs.full_secs)?;
-        let frac_secs = self.frac_secs + rhs.frac_secs;
+        let frac_secs = self.frac_secs() + rhs.frac_secs();
         Self::try_from_parts(full_secs, frac_secs)
     }
 
@@ -293,7 +428,7 @@ impl TimeSpec {
     #[must_use]
     pub fn checked_sub(self, rhs: TimeSpec) -> Option<Self> {
         let full_secs = self.full_secs.checked_sub(rhs.full_secs)?;
-        let frac_secs = self.frac_secs - rhs.frac_secs;
+        let frac_secs = self.frac_secs() - rhs.frac_secs();
         Self::try_from_parts(full_secs, frac_secs)
     }
 
@@ -301,7 +436,7 @@ impl TimeSpec {
     #[must_use]
     pub fn checked_mul(self, rhs: f64) -> Option<Self> {
         let full_secs = self.full_secs as f64 * rhs;
-        let frac_secs = self.frac_secs * rhs + full_secs.fract();
+        let frac_secs = self.frac_secs() * rhs + full_secs.fract();
         if full_secs > i64::MAX as f64 || full_secs < i64::MIN as f64 {
             return None;
         }
@@ -315,7 +450,7 @@ impl TimeSpec {
             return None;
         }
         let full_secs = self.full_secs as f64 / rhs;
-        let frac_secs = self.frac_secs / rhs + full_secs.fract();
+        let frac_secs = self.frac_secs() / rhs + full_secs.fract();
         if full_secs > i64::MAX as f64 || full_secs < i64::MIN as f64 {
             return None;
         }
@@ -344,7 +479,7 @@ impl TryFrom<Duration> for TimeSpec {
 
 impl Display for TimeSpec {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.full_secs as f64 + self.frac_secs)
+        write!(f, "{}", self.full_secs as f64 + self.frac_secs())
     }
 }
 
@@ -352,7 +487,7 @@ impl Neg for TimeSpec {
     type Output = TimeSpec;
 
     fn neg(self) -> Self::Output {
-        TimeSpec::from_parts(-self.full_secs, -self.frac_secs)
+        TimeSpec::from_parts(-self.full_secs, -self.frac_secs())
     }
 }
 
@@ -1,9 +1,17 @@
+use std::{collections::HashMap, ffi::CString};
+
 use crate::Result;
 
 use crate::Usrp;
 
 /// Arguments for specifying a USRP available to the system.
 ///
+/// This same typed builder is reused anywhere UHD accepts a `"key=value"`
+/// argument string: opening a device with [`open`](Self::open), attaching
+/// extra tuning arguments to a [`TuneRequest`](super::TuneRequest), and
+/// passing stream arguments via `with_device_args` on the RX/TX stream
+/// builders.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -22,6 +30,7 @@ pub struct DeviceArgs {
     name: Option<String>,
     type_: Option<String>,
     vid_pid: Option<(String, String)>,
+    extra: HashMap<String, String>,
 }
 
 impl DeviceArgs {
@@ -36,7 +45,7 @@ impl DeviceArgs {
         self
     }
 
-    fn iter(&self) -> impl Iterator<Item = String> + '_ {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = String> + '_ {
         let mut args = vec![];
         if let Some(addr) = &self.addr {
             args.push(format!("args={addr}"));
@@ -57,9 +66,59 @@ impl DeviceArgs {
             args.push(format!("vid={vid}"));
             args.push(format!("pid={pid}"));
         }
+        for (key, value) in &self.extra {
+            args.push(format!("{key}={value}"));
+        }
         args.into_iter()
     }
 
+    /// Set an arbitrary key-value device argument.
+    ///
+    /// This can be used to set arguments not covered by the other methods
+    /// on this type, such as implementation-specific tuning or streaming
+    /// arguments.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if either `key` or `value` contains an `'='`
+    /// character, a `','` character, or a null byte.
+    pub fn arg(mut self, key: &str, value: &str) -> Self {
+        assert!(!key.contains('='), "key cannot contain '='");
+        assert!(!key.contains(','), "key cannot contain ','");
+        assert!(!value.contains('='), "value cannot contain '='");
+        assert!(!value.contains(','), "value cannot contain ','");
+        assert!(!key.contains('\0'), "key cannot contain null bytes");
+        assert!(!value.contains('\0'), "value cannot contain null bytes");
+
+        self.extra.insert(key.to_owned(), value.to_owned());
+        self
+    }
+
+    /// Render this builder as a null-terminated `"key=value,..."` string
+    /// suitable for passing directly to a UHD FFI call.
+    pub(crate) fn to_cstring(&self) -> CString {
+        CString::new(self.to_string()).unwrap()
+    }
+
+    /// Combine `other` into `self`, with `other`'s keys taking precedence
+    /// over any matching key already set on `self`.
+    ///
+    /// Used by callers that build up a [`DeviceArgs`] across more than one
+    /// source (e.g. [`TuneRequest::with_args`](super::TuneRequest::with_args)
+    /// layering a caller-supplied [`DeviceArgs`] on top of one already
+    /// accumulated by earlier builder calls), so neither source silently
+    /// clobbers the other.
+    pub(crate) fn merge(mut self, other: Self) -> Self {
+        self.addr = other.addr.or(self.addr);
+        self.serial = other.serial.or(self.serial);
+        self.resource = other.resource.or(self.resource);
+        self.name = other.name.or(self.name);
+        self.type_ = other.type_.or(self.type_);
+        self.vid_pid = other.vid_pid.or(self.vid_pid);
+        self.extra.extend(other.extra);
+        self
+    }
+
     pub fn name(mut self, name: &str) -> Self {
         self.name = Some(name.to_owned());
         self
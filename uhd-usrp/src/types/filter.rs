@@ -0,0 +1,89 @@
+use std::{ffi::CString, mem::MaybeUninit, ptr::addr_of_mut};
+
+use crate::{ffi::OwnedHandle, try_uhd, Result, UhdError};
+
+/// The kind of analog or digital filter a [`Filter`] describes, as reported
+/// by `uhd_filter_info_base_get_filter_type`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, num_enum::TryFromPrimitive)]
+#[repr(u32)]
+pub enum FilterType {
+    AnalogLowPass = uhd_usrp_sys::uhd_filter_info_base_filter_type_t::UHD_FILTER_ANALOG_LOW_PASS,
+    AnalogBandPass = uhd_usrp_sys::uhd_filter_info_base_filter_type_t::UHD_FILTER_ANALOG_BAND_PASS,
+    DigitalI16 = uhd_usrp_sys::uhd_filter_info_base_filter_type_t::UHD_FILTER_DIGITAL_I16,
+    DigitalComplex = uhd_usrp_sys::uhd_filter_info_base_filter_type_t::UHD_FILTER_DIGITAL_COMPLEX,
+    DigitalFirI16 = uhd_usrp_sys::uhd_filter_info_base_filter_type_t::UHD_FILTER_DIGITAL_FIR_I16,
+    DigitalFirComplex =
+        uhd_usrp_sys::uhd_filter_info_base_filter_type_t::UHD_FILTER_DIGITAL_FIR_COMPLEX,
+    LiquidFirComplex = uhd_usrp_sys::uhd_filter_info_base_filter_type_t::UHD_FILTER_LIQUID,
+}
+
+/// A single entry in a channel's analog/digital filter chain.
+///
+/// Obtain one from [`ChannelConfiguration::filter`](crate::usrp::ChannelConfiguration::filter)
+/// and replay edits (e.g. toggling [`bypass`](Self::bypass)) with
+/// [`ChannelConfigurationBuilder::set_filter`](crate::usrp::ChannelConfigurationBuilder::set_filter).
+///
+/// Filter chains let you shape a channel's passband beyond what
+/// `set_bandwidth` alone controls, e.g. bypassing an analog anti-aliasing
+/// stage or adjusting a digital FIR's roll-off.
+pub struct Filter {
+    handle: OwnedHandle<uhd_usrp_sys::uhd_filter_info_base_t>,
+}
+
+impl Filter {
+    pub(crate) fn from_handle(handle: OwnedHandle<uhd_usrp_sys::uhd_filter_info_base_t>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) fn handle(&self) -> &OwnedHandle<uhd_usrp_sys::uhd_filter_info_base_t> {
+        &self.handle
+    }
+
+    /// The kind of filter this is (analog, digital, FIR, ...).
+    pub fn filter_type(&self) -> Result<FilterType> {
+        let mut ty: MaybeUninit<uhd_usrp_sys::uhd_filter_info_base_filter_type_t::Type> =
+            MaybeUninit::uninit();
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_filter_info_base_get_filter_type(
+                self.handle.as_mut_ptr(),
+                ty.as_mut_ptr(),
+            )
+        })?;
+        FilterType::try_from(unsafe { ty.assume_init() }).or(Err(UhdError::Unknown))
+    }
+
+    /// Whether this filter stage can be bypassed.
+    pub fn is_bypassable(&self) -> Result<bool> {
+        let mut bypassable = false;
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_filter_info_base_is_bypassable(
+                self.handle.as_mut_ptr(),
+                addr_of_mut!(bypassable),
+            )
+        })?;
+        Ok(bypassable)
+    }
+
+    /// Whether this filter stage is currently bypassed.
+    pub fn is_bypassed(&self) -> Result<bool> {
+        let mut bypassed = false;
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_filter_info_base_is_bypassed(
+                self.handle.as_mut_ptr(),
+                addr_of_mut!(bypassed),
+            )
+        })?;
+        Ok(bypassed)
+    }
+
+    /// Set whether this filter stage is bypassed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this filter isn't [bypassable](Self::is_bypassable).
+    pub fn set_bypass(&mut self, bypass: bool) -> Result<()> {
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_filter_info_base_set_bypass(self.handle.as_mut_ptr(), bypass)
+        })
+    }
+}
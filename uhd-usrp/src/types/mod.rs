@@ -1,13 +1,11 @@
 mod device_args;
-mod metadata;
+mod filter;
 mod range;
 mod sensor;
-mod time;
 mod tune;
 
 pub use device_args::DeviceArgs;
-pub use metadata::{RxErrorCode, RxMetadata, TxMetadata, TxMetadataBuilder};
+pub use filter::{Filter, FilterType};
 pub use range::{MetaRange, Range};
-pub use sensor::SensorValue;
-pub use time::TimeSpec;
-pub use tune::{TuneRequest, TuneResult};
+pub use sensor::{SensorDataType, SensorValue, SensorValueValue};
+pub use tune::{LoTuningMode, TuneRequest, TuneResult};
@@ -5,6 +5,7 @@ use crate::{ffi::OwnedHandle, try_uhd, Result};
 /// A range object describes a set of discrete values of the form:
 /// `y = start + step*n`, where `n` is an integer between `0` and `(stop - start)/step`.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Range {
     /// The minimum value for this range.
     pub start: f64,
@@ -20,28 +20,45 @@ pub enum SensorValueValue {
     String(String),
 }
 
+/// The underlying data type of a [`SensorValue`], as reported by
+/// `uhd_sensor_value_data_type`.
+///
+/// This is the discriminant [`SensorValueValue::from_handle`] switches on
+/// to decide which accessor (`uhd_sensor_value_to_bool`/`to_realnum`/
+/// `to_int`/`value`) is safe to call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SensorDataType {
+    Boolean,
+    Real,
+    Integer,
+    String,
+}
+
 impl SensorValue {
     /// Try to create a new sensor value from a C handle.
     pub(crate) fn from_handle(
         handle: &OwnedHandle<uhd_usrp_sys::uhd_sensor_value_t>,
     ) -> Result<Self> {
-        let mut buf = FfiString::with_capacity(32);
-        unsafe {
-            uhd_usrp_sys::uhd_sensor_value_unit(
-                handle.as_mut_ptr(),
-                buf.as_mut_ptr(),
-                buf.max_chars(),
-            );
-        }
-        let unit = buf.to_string()?;
-        unsafe {
-            uhd_usrp_sys::uhd_sensor_value_name(
-                handle.as_mut_ptr(),
-                buf.as_mut_ptr(),
-                buf.max_chars(),
-            );
-        }
-        let name = buf.to_string()?;
+        let unit = FfiString::get_with_retry(32, 4096, |buf| {
+            unsafe {
+                uhd_usrp_sys::uhd_sensor_value_unit(
+                    handle.as_mut_ptr(),
+                    buf.as_mut_ptr(),
+                    buf.max_chars(),
+                );
+            }
+            Ok(())
+        })?;
+        let name = FfiString::get_with_retry(32, 4096, |buf| {
+            unsafe {
+                uhd_usrp_sys::uhd_sensor_value_name(
+                    handle.as_mut_ptr(),
+                    buf.as_mut_ptr(),
+                    buf.max_chars(),
+                );
+            }
+            Ok(())
+        })?;
         Ok(Self {
             kind: SensorValueValue::from_handle(handle)?,
             unit,
@@ -67,6 +84,11 @@ impl SensorValue {
         &self.kind
     }
 
+    /// The underlying data type of this sensor's value.
+    pub fn data_type(&self) -> SensorDataType {
+        self.kind.data_type()
+    }
+
     /// Returns `Some` if the value is a boolean, `None` otherwise.
     pub fn as_bool(&self) -> Option<bool> {
         match self.kind {
@@ -91,6 +113,20 @@ impl SensorValue {
             _ => None,
         }
     }
+
+    /// Returns `Some` if the value is an integer, widened to `i64`,
+    /// `None` otherwise.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_i32().map(i64::from)
+    }
+
+    /// Returns `Some` if the value is a string, `None` otherwise.
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.kind {
+            SensorValueValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for SensorValue {
@@ -105,6 +141,16 @@ impl std::fmt::Display for SensorValue {
 }
 
 impl SensorValueValue {
+    /// The underlying data type this value was decoded from.
+    pub fn data_type(&self) -> SensorDataType {
+        match self {
+            SensorValueValue::Boolean(_) => SensorDataType::Boolean,
+            SensorValueValue::Real(_) => SensorDataType::Real,
+            SensorValueValue::Integer(_) => SensorDataType::Integer,
+            SensorValueValue::String(_) => SensorDataType::String,
+        }
+    }
+
     pub(crate) fn from_handle(
         handle: &OwnedHandle<uhd_usrp_sys::uhd_sensor_value_t>,
     ) -> Result<Self> {
@@ -142,11 +188,16 @@ impl SensorValueValue {
                 Ok(Self::Integer(val))
             }
             uhd_usrp_sys::uhd_sensor_value_data_type_t::UHD_SENSOR_VALUE_STRING => {
-                let mut val = FfiString::with_capacity(32);
-                try_uhd!(unsafe {
-                    uhd_usrp_sys::uhd_sensor_value_value(handle.as_mut_ptr(), val.as_mut_ptr(), val.max_chars())
+                let val = FfiString::get_with_retry(32, 4096, |val| {
+                    try_uhd!(unsafe {
+                        uhd_usrp_sys::uhd_sensor_value_value(
+                            handle.as_mut_ptr(),
+                            val.as_mut_ptr(),
+                            val.max_chars(),
+                        )
+                    })
                 })?;
-                Ok(Self::String(val.to_string()?))
+                Ok(Self::String(val))
             }
             _ => Err(UhdError::NotImplemented),
         }
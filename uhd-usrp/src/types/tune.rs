@@ -9,15 +9,40 @@ use uhd_usrp_sys::uhd_tune_request_policy_t::*;
 
 use crate::ffi::FfiString;
 
+use super::DeviceArgs;
+
 /// A tune request instructs the implementation how to tune the RF chain.
 ///
 /// The policies can be used to select automatic tuning or fine control
 /// over the daughterboard IF and DSP tuning. Not all combinations of
 /// policies are applicable. Convenience constructors are supplied for
 /// most use cases.
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct TuneRequest {
     inner: uhd_usrp_sys::uhd_tune_request_t,
+    /// The args accumulated so far by [`with_args`](Self::with_args)/[`arg`](Self::arg),
+    /// kept around so `arg` can add one key at a time without clobbering
+    /// previously set ones.
+    device_args: DeviceArgs,
+    /// Keeps the C string backing `inner.args` alive for the lifetime of
+    /// this request.
+    args: Option<CString>,
+}
+
+impl Clone for TuneRequest {
+    fn clone(&self) -> Self {
+        let args = self.args.clone();
+        let mut inner = self.inner;
+        inner.args = args
+            .as_ref()
+            .map(|a| a.as_ptr() as *mut _)
+            .unwrap_or(std::ptr::null_mut());
+        Self {
+            inner,
+            device_args: self.device_args.clone(),
+            args,
+        }
+    }
 }
 
 /// Contains the RF and DSP tuned frequencies.
@@ -32,7 +57,7 @@ impl TuneRequest {
     /// Defaults to an automatic policy for the RF and DSP frequency to tune the chain
     /// as close as possible to the target frequency.
     ///
-    /// Note: there is currently no support for specifying additional arguments.
+    /// Use [`with_args`](Self::with_args) to attach additional arguments.
     pub fn new(target_freq: f64) -> Self {
         Self {
             inner: uhd_usrp_sys::uhd_tune_request_t {
@@ -41,9 +66,10 @@ impl TuneRequest {
                 rf_freq: 0.0,
                 dsp_freq_policy: UHD_TUNE_REQUEST_POLICY_AUTO,
                 dsp_freq: 0.0,
-                /// TODO: add support for args
                 args: std::ptr::null_mut(),
             },
+            device_args: DeviceArgs::new(),
+            args: None,
         }
     }
 
@@ -116,6 +142,65 @@ impl TuneRequest {
         self.inner.rf_freq_policy = UHD_TUNE_REQUEST_POLICY_NONE;
         self
     }
+
+    /// Attach additional implementation-specific arguments to this tune
+    /// request, such as selecting integer-N mode or an LO source.
+    ///
+    /// Merges into any args previously set by `with_args`/[`arg`](Self::arg),
+    /// with `args`'s keys taking precedence over matching ones already set.
+    /// See [`DeviceArgs::arg`] for setting keys that aren't covered by a
+    /// dedicated method.
+    pub fn with_args(mut self, args: DeviceArgs) -> Self {
+        self.device_args = std::mem::take(&mut self.device_args).merge(args);
+        self.sync_args();
+        self
+    }
+
+    /// Attach a single implementation-specific tuning argument, such as
+    /// `("mode_n", "integer")` to select integer-N synthesis.
+    ///
+    /// Like [`with_args`](Self::with_args), this merges into any args
+    /// already set, so repeated calls accumulate keys instead of
+    /// overwriting them.
+    pub fn arg(mut self, key: &str, value: &str) -> Self {
+        self.device_args = std::mem::take(&mut self.device_args).arg(key, value);
+        self.sync_args();
+        self
+    }
+
+    /// Rebuild `inner.args`/`args` from the current `device_args`.
+    fn sync_args(&mut self) {
+        let args = self.device_args.to_cstring();
+        self.inner.args = args.as_ptr() as *mut _;
+        self.args = Some(args);
+    }
+
+    /// Select the RF synthesizer's tuning mode.
+    ///
+    /// This is passed through as the `mode_n` tuning argument, which most
+    /// daughterboards understand.
+    pub fn with_lo_tuning_mode(self, mode: LoTuningMode) -> Self {
+        match mode {
+            LoTuningMode::Fractional => self,
+            LoTuningMode::IntegerN => self.with_args(DeviceArgs::new().arg("mode_n", "integer")),
+        }
+    }
+}
+
+/// Selects between fractional-N and integer-N tuning for the RF LO
+/// synthesizer.
+///
+/// Fractional-N gives fine frequency resolution but can introduce
+/// fractional spurs near the LO; integer-N avoids those spurs at the cost
+/// of coarser tuning resolution. Used together with
+/// [`TuneRequest::with_lo_tuning_mode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LoTuningMode {
+    /// Fine resolution, may introduce fractional-N spurs near the LO (default).
+    #[default]
+    Fractional,
+    /// Coarser resolution, but avoids fractional-N spurs.
+    IntegerN,
 }
 
 impl TuneResult {
@@ -0,0 +1,164 @@
+use crate::{
+    types::{TuneRequest, TuneResult},
+    usrp::Usrp,
+    Result, UhdError,
+};
+
+use super::{read::ChannelConfiguration, write::ChannelConfigurationBuilder};
+
+/// The error returned by a [`ChannelGroupBuilder`] operation, identifying
+/// which channel in the group caused the failure.
+///
+/// Operations on a group short-circuit on the first failing channel, so
+/// channels after `channel` in the group were left unconfigured.
+#[derive(thiserror::Error, Debug)]
+#[error("channel {channel} failed: {source}")]
+pub struct ChannelGroupError {
+    /// The index of the channel that failed.
+    pub channel: usize,
+    /// The underlying error returned for that channel.
+    #[source]
+    pub source: UhdError,
+}
+
+/// Applies the same configuration to a group of channels as a single
+/// logical operation, for MIMO setups where several channels need to
+/// share a sample rate, bandwidth, or LO.
+///
+/// Each call fans the corresponding [`ChannelConfigurationBuilder`]
+/// method out to every channel in the group, in order, and stops at the
+/// first channel that returns an error.
+pub struct ChannelGroupBuilder<'usrp, const D: usize> {
+    usrp: &'usrp Usrp,
+    channels: Vec<usize>,
+}
+
+impl<'usrp, const D: usize> ChannelGroupBuilder<'usrp, D> {
+    pub(crate) fn new(usrp: &'usrp Usrp, channels: &[usize]) -> Self {
+        Self {
+            usrp,
+            channels: channels.to_vec(),
+        }
+    }
+
+    /// The channel indices in this group, in the order they were given.
+    pub fn channels(&self) -> &[usize] {
+        &self.channels
+    }
+
+    fn for_each(
+        &self,
+        mut f: impl FnMut(ChannelConfigurationBuilder<'usrp, D>) -> crate::Result<()>,
+    ) -> Result<&Self, ChannelGroupError> {
+        for &channel in &self.channels {
+            f(ChannelConfigurationBuilder::new(self.usrp, channel))
+                .map_err(|source| ChannelGroupError { channel, source })?;
+        }
+        Ok(self)
+    }
+
+    /// Apply a per-channel value to every channel in the group, pairing
+    /// `values[i]` with `channels()[i]`.
+    ///
+    /// If `values` is shorter than the group, only the first `values.len()`
+    /// channels are configured; extra values beyond the group's length are
+    /// ignored.
+    fn for_each_with<V: Copy>(
+        &self,
+        values: &[V],
+        mut f: impl FnMut(ChannelConfigurationBuilder<'usrp, D>, V) -> crate::Result<()>,
+    ) -> Result<&Self, ChannelGroupError> {
+        for (&channel, &value) in self.channels.iter().zip(values) {
+            f(ChannelConfigurationBuilder::new(self.usrp, channel), value)
+                .map_err(|source| ChannelGroupError { channel, source })?;
+        }
+        Ok(self)
+    }
+
+    fn read_each<V>(
+        &self,
+        mut f: impl FnMut(ChannelConfiguration<'usrp, D>) -> crate::Result<V>,
+    ) -> Result<Vec<V>, ChannelGroupError> {
+        self.channels
+            .iter()
+            .map(|&channel| {
+                f(ChannelConfiguration::new(self.usrp, channel))
+                    .map_err(|source| ChannelGroupError { channel, source })
+            })
+            .collect()
+    }
+
+    /// Set the bandwidth, in Hz, on every channel in the group.
+    pub fn set_bandwidth(&self, bw: f64) -> Result<&Self, ChannelGroupError> {
+        self.for_each(|c| c.set_bandwidth(bw).map(|_| ()))
+    }
+
+    /// Set the antenna on every channel in the group.
+    pub fn set_antenna(&self, name: &str) -> Result<&Self, ChannelGroupError> {
+        self.for_each(|c| c.set_antenna(name).map(|_| ()))
+    }
+
+    /// Set the gain, in dB, on every channel in the group.
+    ///
+    /// See [`ChannelConfigurationBuilder::set_gain`] for the meaning of `name`.
+    pub fn set_gain(&self, name: Option<&str>, gain: f64) -> Result<&Self, ChannelGroupError> {
+        self.for_each(|c| c.set_gain(name, gain).map(|_| ()))
+    }
+
+    /// Set a different gain, in dB, on each channel in the group. See
+    /// [`for_each_with`](Self::for_each_with) for how `gains` is paired
+    /// with [`channels()`](Self::channels).
+    pub fn set_gains(&self, name: Option<&str>, gains: &[f64]) -> Result<&Self, ChannelGroupError> {
+        self.for_each_with(gains, |c, gain| c.set_gain(name, gain).map(|_| ()))
+    }
+
+    /// Set the sample rate, in samples per second, on every channel in the group.
+    pub fn set_sample_rate(&self, rate: f64) -> Result<&Self, ChannelGroupError> {
+        self.for_each(|c| c.set_sample_rate(rate).map(|_| ()))
+    }
+
+    /// Set a different sample rate on each channel in the group. See
+    /// [`for_each_with`](Self::for_each_with) for how `rates` is paired
+    /// with [`channels()`](Self::channels).
+    pub fn set_sample_rates(&self, rates: &[f64]) -> Result<&Self, ChannelGroupError> {
+        self.for_each_with(rates, |c, rate| c.set_sample_rate(rate).map(|_| ()))
+    }
+
+    /// Set the center frequency, in Hz, on every channel in the group.
+    pub fn set_center_freq(&self, freq: f64) -> Result<&Self, ChannelGroupError> {
+        self.for_each(|c| c.set_center_freq(freq).map(|_| ()))
+    }
+
+    /// Set a different center frequency on each channel in the group. See
+    /// [`for_each_with`](Self::for_each_with) for how `freqs` is paired
+    /// with [`channels()`](Self::channels).
+    pub fn set_center_freqs(&self, freqs: &[f64]) -> Result<&Self, ChannelGroupError> {
+        self.for_each_with(freqs, |c, freq| c.set_center_freq(freq).map(|_| ()))
+    }
+
+    /// Read back the center frequency of every channel in the group, in
+    /// channel order.
+    pub fn center_freqs(&self) -> Result<Vec<f64>, ChannelGroupError> {
+        self.read_each(|c| c.center_freq())
+    }
+
+    /// Read back the gain of every channel in the group, in channel order.
+    ///
+    /// See [`ChannelConfiguration::gain`] for the meaning of `name`.
+    pub fn gains(&self, name: Option<&str>) -> Result<Vec<f64>, ChannelGroupError> {
+        self.read_each(|c| c.gain(name))
+    }
+
+    /// Tune every channel in the group with the same [`TuneRequest`],
+    /// collecting the per-channel [`TuneResult`]s in channel order.
+    pub fn tune(&self, req: &TuneRequest) -> Result<Vec<TuneResult>, ChannelGroupError> {
+        let mut results = Vec::with_capacity(self.channels.len());
+        for &channel in &self.channels {
+            let (_, result) = ChannelConfigurationBuilder::<D>::new(self.usrp, channel)
+                .tune_coerced(req)
+                .map_err(|source| ChannelGroupError { channel, source })?;
+            results.push(result);
+        }
+        Ok(results)
+    }
+}
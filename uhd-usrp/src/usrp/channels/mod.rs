@@ -1,8 +1,33 @@
+mod group;
+mod monitor;
 mod read;
+mod settings;
+mod snapshot;
 mod write;
 
-pub use read::ChannelConfiguration;
+pub use group::{ChannelGroupBuilder, ChannelGroupError};
+pub use monitor::{SensorCondition, SensorEvent, SensorMonitor, SensorReading};
+pub use read::{ChannelConfiguration, LockWaitError};
+pub use settings::ChannelSettings;
+pub use snapshot::ChannelSnapshot;
 pub use write::ChannelConfigurationBuilder;
 
 pub(crate) const TX_DIR: usize = 0;
-pub(crate) const RX_DIR: usize = 1;
\ No newline at end of file
+pub(crate) const RX_DIR: usize = 1;
+
+/// A direction-tagged channel index, identifying one RX or TX channel on a
+/// [`Usrp`](super::Usrp).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Channel {
+    Rx(usize),
+    Tx(usize),
+}
+
+impl std::fmt::Display for Channel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Channel::Rx(i) => write!(f, "RX {i}"),
+            Channel::Tx(i) => write!(f, "TX {i}"),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,248 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use super::{read::ChannelConfiguration, Channel, RX_DIR, TX_DIR};
+use crate::{
+    types::{SensorValue, SensorValueValue},
+    usrp::Usrp,
+    UhdError,
+};
+
+/// One timestamped sensor reading emitted by a [`SensorMonitor`].
+///
+/// `value` is an `Err` when the sensor could not be read for this poll
+/// (e.g. it momentarily disappeared); the monitor keeps polling regardless.
+pub struct SensorReading {
+    /// The sensor this reading is for.
+    pub sensor: String,
+    /// The reading itself, or the error encountered while polling it.
+    pub value: Result<SensorValue, UhdError>,
+    /// When this reading was taken.
+    pub timestamp: Instant,
+}
+
+/// A condition under which a watched sensor fires a [`SensorEvent`],
+/// evaluated against the previous and current reading for that sensor.
+///
+/// The first reading for a sensor never fires an event, since there is no
+/// previous value to compare against.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SensorCondition {
+    /// For boolean sensors (e.g. `ref_locked`, `lo_locked`): fire whenever
+    /// the reading differs from the previous one.
+    StateChange,
+    /// For real-valued or integer sensors: fire when the reading crosses
+    /// `threshold`, i.e. the previous and current readings fall on
+    /// opposite sides of it.
+    CrossesThreshold(f64),
+    /// For real-valued or integer sensors: fire when the reading crosses
+    /// into or out of the `min..=max` window.
+    LeavesWindow(f64, f64),
+}
+
+impl SensorCondition {
+    fn triggers(self, old: &SensorValueValue, new: &SensorValueValue) -> bool {
+        match self {
+            SensorCondition::StateChange => old != new,
+            SensorCondition::CrossesThreshold(threshold) => {
+                match (as_f64(old), as_f64(new)) {
+                    (Some(o), Some(n)) => (o < threshold) != (n < threshold),
+                    _ => false,
+                }
+            }
+            SensorCondition::LeavesWindow(min, max) => match (as_f64(old), as_f64(new)) {
+                (Some(o), Some(n)) => (min..=max).contains(&o) != (min..=max).contains(&n),
+                _ => false,
+            },
+        }
+    }
+}
+
+fn as_f64(value: &SensorValueValue) -> Option<f64> {
+    match value {
+        SensorValueValue::Real(f) => Some(*f),
+        SensorValueValue::Integer(i) => Some(*i as f64),
+        _ => None,
+    }
+}
+
+/// Fired by a watched sensor's [`SensorCondition`] when a new reading
+/// triggers it; see [`SensorMonitor::watch`].
+pub struct SensorEvent {
+    /// The sensor this event is for.
+    pub sensor: String,
+    /// The previous reading's value.
+    pub old: SensorValueValue,
+    /// The reading that triggered this event.
+    pub new: SensorValueValue,
+}
+
+/// Polls a set of sensors on a background thread at a fixed interval,
+/// emitting [`SensorReading`]s over a channel.
+///
+/// This is for long captures where you want to log things like
+/// `lo_locked`, `ref_locked`, or temperature continuously without
+/// hand-rolling a polling thread. The watched sensor names can be changed
+/// at any time from the calling thread via [`SensorMonitor::add_sensor`]
+/// and [`SensorMonitor::remove_sensor`]. Dropping the monitor (or calling
+/// [`SensorMonitor::stop`]) signals the background thread to stop;
+/// [`SensorMonitor::stop`] also hands the [`Usrp`] back, while dropping
+/// the monitor drops it.
+///
+/// Sensors can additionally be watched with a [`SensorCondition`] via
+/// [`SensorMonitor::watch`], so a reference unlock or thermal excursion
+/// during a long capture shows up as a [`SensorEvent`] on
+/// [`SensorMonitor::events`] instead of requiring the caller to re-derive
+/// it from every [`SensorReading`].
+pub struct SensorMonitor {
+    names: Arc<Mutex<Vec<String>>>,
+    conditions: Arc<Mutex<HashMap<String, SensorCondition>>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<Usrp>>,
+    readings: mpsc::Receiver<SensorReading>,
+    events: mpsc::Receiver<SensorEvent>,
+}
+
+impl SensorMonitor {
+    /// Start polling `names` on `channel` every `interval`, taking
+    /// ownership of `usrp` for the lifetime of the monitor.
+    pub fn start(
+        usrp: Usrp,
+        channel: Channel,
+        names: Vec<String>,
+        interval: Duration,
+    ) -> Self {
+        let names = Arc::new(Mutex::new(names));
+        let conditions = Arc::new(Mutex::new(HashMap::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, readings) = mpsc::channel();
+        let (event_sender, events) = mpsc::channel();
+
+        let thread_names = Arc::clone(&names);
+        let thread_conditions = Arc::clone(&conditions);
+        let thread_stop = Arc::clone(&stop);
+        let handle = thread::spawn(move || {
+            let mut last: HashMap<String, SensorValueValue> = HashMap::new();
+            while !thread_stop.load(Ordering::Relaxed) {
+                let watched = thread_names.lock().unwrap().clone();
+                for sensor in watched {
+                    let value = match channel {
+                        Channel::Rx(i) => {
+                            ChannelConfiguration::<'_, RX_DIR>::new(&usrp, i).sensor_value(&sensor)
+                        }
+                        Channel::Tx(i) => {
+                            ChannelConfiguration::<'_, TX_DIR>::new(&usrp, i).sensor_value(&sensor)
+                        }
+                    };
+
+                    if let Ok(v) = &value {
+                        let new = v.value().clone();
+                        let condition = thread_conditions.lock().unwrap().get(&sensor).copied();
+                        if let Some(condition) = condition {
+                            let fired = last
+                                .get(&sensor)
+                                .is_some_and(|old| condition.triggers(old, &new));
+                            if fired {
+                                let event = SensorEvent {
+                                    sensor: sensor.clone(),
+                                    old: last[&sensor].clone(),
+                                    new: new.clone(),
+                                };
+                                if event_sender.send(event).is_err() {
+                                    return usrp;
+                                }
+                            }
+                        }
+                        last.insert(sensor.clone(), new);
+                    }
+
+                    let reading = SensorReading {
+                        sensor,
+                        value,
+                        timestamp: Instant::now(),
+                    };
+                    if sender.send(reading).is_err() {
+                        // Receiver dropped; no point continuing to poll.
+                        return usrp;
+                    }
+                }
+                thread::sleep(interval);
+            }
+            usrp
+        });
+
+        Self {
+            names,
+            conditions,
+            stop,
+            handle: Some(handle),
+            readings,
+            events,
+        }
+    }
+
+    /// Start watching an additional sensor.
+    pub fn add_sensor(&self, name: impl Into<String>) {
+        self.names.lock().unwrap().push(name.into());
+    }
+
+    /// Stop watching a sensor by name. Also clears any [`SensorCondition`]
+    /// registered for it via [`Self::watch`].
+    pub fn remove_sensor(&self, name: &str) {
+        self.names.lock().unwrap().retain(|n| n != name);
+        self.conditions.lock().unwrap().remove(name);
+    }
+
+    /// Watch `name` for `condition`, adding it to the polled set if it
+    /// isn't already watched. A matching reading is delivered as a
+    /// [`SensorEvent`] on [`Self::events`], in addition to showing up as an
+    /// ordinary [`SensorReading`].
+    pub fn watch(&self, name: impl Into<String>, condition: SensorCondition) {
+        let name = name.into();
+        if !self.names.lock().unwrap().iter().any(|n| n == &name) {
+            self.add_sensor(name.clone());
+        }
+        self.conditions.lock().unwrap().insert(name, condition);
+    }
+
+    /// Stop firing [`SensorEvent`]s for `name`, without un-watching the
+    /// sensor's plain readings.
+    pub fn unwatch(&self, name: &str) {
+        self.conditions.lock().unwrap().remove(name);
+    }
+
+    /// The receiving end of the readings channel. Recv in a loop (or
+    /// iterate it directly, since [`mpsc::Receiver`] is an [`Iterator`])
+    /// to consume readings as they arrive.
+    pub fn readings(&self) -> &mpsc::Receiver<SensorReading> {
+        &self.readings
+    }
+
+    /// The receiving end of the [`SensorEvent`] channel, fed by sensors
+    /// registered through [`Self::watch`].
+    pub fn events(&self) -> &mpsc::Receiver<SensorEvent> {
+        &self.events
+    }
+
+    /// Stop the background thread and return the [`Usrp`] it owned.
+    pub fn stop(mut self) -> Usrp {
+        self.stop.store(true, Ordering::Relaxed);
+        self.handle.take().unwrap().join().unwrap()
+    }
+}
+
+impl Drop for SensorMonitor {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
@@ -1,14 +1,37 @@
-use std::{ffi::CString, mem::MaybeUninit, ptr::addr_of_mut};
+use std::{
+    ffi::CString,
+    mem::MaybeUninit,
+    ptr::addr_of_mut,
+    thread,
+    time::{Duration, Instant},
+};
+
+use num_complex::Complex;
 
-use super::{RX_DIR, TX_DIR};
+use super::{ChannelSettings, RX_DIR, TX_DIR};
 use crate::{
     error::try_uhd,
     ffi::{FfiString, FfiStringVec, OwnedHandle},
-    types::{MetaRange, SensorValue},
-    usrp::{Usrp, HardwareInfo},
-    Result,
+    types::{Filter, MetaRange, SensorValue},
+    usrp::{HardwareInfo, SubdevPair, Usrp},
+    Result, UhdError,
 };
 
+/// The error returned when a channel sensor fails to report locked within
+/// the requested timeout.
+#[derive(thiserror::Error, Debug)]
+pub enum LockWaitError {
+    /// Querying the sensor itself failed.
+    #[error(transparent)]
+    Uhd(#[from] UhdError),
+    /// The sensor never reported locked before the deadline.
+    #[error("sensor {sensor} did not report locked before the timeout elapsed")]
+    Timeout {
+        /// The name of the sensor that timed out.
+        sensor: String,
+    },
+}
+
 // D parameter is a hack until const enum generics are stabilized
 pub struct ChannelConfiguration<'usrp, const D: usize> {
     /// The USRP acted upon.
@@ -76,6 +99,13 @@ impl<'usrp, const D: usize> ChannelConfiguration<'usrp, D> {
         .and_then(|_| Ok(unsafe { result.assume_init() }))
     }
 
+    /// Alias for [`bandwidth_ranges`](Self::bandwidth_ranges), naming the
+    /// channel's overall bandwidth range the way [`gain_range`](Self::gain_range)
+    /// and [`freq_range`](Self::freq_range) name their own ranges.
+    pub fn bandwidth_range(&self) -> Result<MetaRange> {
+        self.bandwidth_ranges()
+    }
+
     /// Get all possible bandwidth ranges for the channel's frontend.
     pub fn bandwidth_ranges(&self) -> Result<MetaRange> {
         let handle = OwnedHandle::<uhd_usrp_sys::uhd_meta_range_t>::new(
@@ -97,6 +127,13 @@ impl<'usrp, const D: usize> ChannelConfiguration<'usrp, D> {
         MetaRange::from_handle(handle)
     }
 
+    /// Alias for [`center_freq_ranges`](Self::center_freq_ranges), naming
+    /// the tunable range the way [`gain_range`](Self::gain_range) and
+    /// [`bandwidth_range`](Self::bandwidth_range) name their own ranges.
+    pub fn freq_range(&self) -> Result<MetaRange> {
+        self.center_freq_ranges()
+    }
+
     /// Get the channel's center frequency.
     pub fn center_freq(&self) -> Result<f64> {
         let mut result = std::mem::MaybeUninit::uninit();
@@ -207,6 +244,33 @@ impl<'usrp, const D: usize> ChannelConfiguration<'usrp, D> {
         MetaRange::from_handle(handle)
     }
 
+    /// Alias for [`gain_ranges(None)`](Self::gain_ranges), naming the
+    /// overall (not per-element) gain range the way [`bandwidth_range`](Self::bandwidth_range)
+    /// and [`freq_range`](Self::freq_range) name their own overall ranges.
+    pub fn gain_range(&self) -> Result<MetaRange> {
+        self.gain_ranges(None)
+    }
+
+    /// Enumerate the names of the distinct gain elements ("stages") this
+    /// channel's frontend exposes, for use with [`gain`](Self::gain)'s
+    /// `name` parameter.
+    pub fn gain_names(&self) -> Result<Vec<String>> {
+        let mut names = FfiStringVec::new();
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_gain_names,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_gain_names,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                self.channel,
+                names.as_mut_ptr(),
+            )
+        })?;
+        Ok(names.to_vec())
+    }
+
     /// Fetch names, serial numbers, etc. of the channel's hardware.
     pub fn hardware_info(&self) -> Result<HardwareInfo> {
         match D {
@@ -387,6 +451,73 @@ impl<'usrp, const D: usize> ChannelConfiguration<'usrp, D> {
         .and_then(|_| Ok(unsafe { result.assume_init() }))
     }
 
+    /// Returns true if the device has a power reference for this channel,
+    /// i.e. whether [`ChannelConfigurationBuilder::set_power_reference`](super::ChannelConfigurationBuilder::set_power_reference)
+    /// is supported.
+    ///
+    /// Only devices with a power calibration table (e.g. most Ettus Zynq/RFNoC
+    /// devices) support setting an absolute, calibrated output/input power.
+    pub fn has_power_reference(&self) -> Result<bool> {
+        let mut result = false;
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_has_rx_power_reference,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_has_tx_power_reference,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                self.channel,
+                addr_of_mut!(result),
+            )
+        })?;
+        Ok(result)
+    }
+
+    /// Get the current calibrated power reference level, in dBm.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device does not support a power reference;
+    /// check [`has_power_reference`](Self::has_power_reference) first.
+    pub fn power_reference(&self) -> Result<f64> {
+        let mut result = std::mem::MaybeUninit::uninit();
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_power_reference,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_power_reference,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                self.channel,
+                result.as_mut_ptr(),
+            )
+        })
+        .and_then(|_| Ok(unsafe { result.assume_init() }))
+    }
+
+    /// Get the valid range of calibrated power reference levels, in dBm.
+    pub fn power_range(&self) -> Result<MetaRange> {
+        let handle = OwnedHandle::<uhd_usrp_sys::uhd_meta_range_t>::new(
+            uhd_usrp_sys::uhd_meta_range_make,
+            uhd_usrp_sys::uhd_meta_range_free,
+        )?;
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_power_range,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_power_range,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                self.channel,
+                handle.as_mut_ptr(),
+            )
+        })?;
+        MetaRange::from_handle(handle)
+    }
+
     /// Get a range of possible sample rates.
     pub fn sample_rates(&self) -> Result<MetaRange> {
         let handle = OwnedHandle::<uhd_usrp_sys::uhd_meta_range_t>::new(
@@ -442,8 +573,8 @@ impl<'usrp, const D: usize> ChannelConfiguration<'usrp, D> {
             uhd_usrp_sys::uhd_sensor_value_free,
         )?;
         let f = match D {
-            RX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_sensor,
-            TX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_sensor,
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_sensor,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_sensor,
             _ => unreachable!(),
         };
         try_uhd!(unsafe {
@@ -457,6 +588,176 @@ impl<'usrp, const D: usize> ChannelConfiguration<'usrp, D> {
         Ok(SensorValue::new(handle))
     }
 
+    /// Poll a sensor until `predicate` returns `true` for its value or
+    /// `timeout` elapses, sleeping `poll_interval` between reads.
+    ///
+    /// This is the general form behind [`wait_for_sensor_locked`](Self::wait_for_sensor_locked),
+    /// [`wait_for_lo_locked`](Self::wait_for_lo_locked), and
+    /// [`wait_for_ref_locked`](Self::wait_for_ref_locked); use it directly
+    /// for non-boolean sensors, e.g. waiting for a temperature reading to
+    /// drop below a threshold.
+    pub fn wait_for_sensor(
+        &self,
+        name: &str,
+        predicate: impl Fn(&SensorValue) -> bool,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<SensorValue, LockWaitError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let value = self.sensor_value(name)?;
+            if predicate(&value) {
+                return Ok(value);
+            }
+            if Instant::now() >= deadline {
+                return Err(LockWaitError::Timeout {
+                    sensor: name.to_string(),
+                });
+            }
+            thread::sleep(poll_interval.min(deadline.saturating_duration_since(Instant::now())));
+        }
+    }
+
+    /// Poll a boolean sensor until it reads `true` or `timeout` elapses.
+    ///
+    /// Useful for waiting on `lo_locked`/`ref_locked` style sensors to
+    /// settle after retuning or switching clock source, instead of
+    /// sleeping a fixed duration before streaming.
+    pub fn wait_for_sensor_locked(
+        &self,
+        name: &str,
+        timeout: Duration,
+    ) -> Result<SensorValue, LockWaitError> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(10);
+        self.wait_for_sensor(name, |v| v.as_bool() == Some(true), timeout, POLL_INTERVAL)
+    }
+
+    /// Wait for the `lo_locked` sensor to report locked.
+    ///
+    /// This lets tuning code reliably gate streaming start on PLL lock
+    /// instead of sleeping a fixed duration.
+    pub fn wait_for_lo_locked(&self, timeout: Duration) -> Result<SensorValue, LockWaitError> {
+        self.wait_for_sensor_locked("lo_locked", timeout)
+    }
+
+    /// Wait for the `ref_locked` sensor to report locked.
+    ///
+    /// See [`wait_for_lo_locked`](Self::wait_for_lo_locked) for why this
+    /// is preferable to a fixed sleep.
+    pub fn wait_for_ref_locked(&self, timeout: Duration) -> Result<SensorValue, LockWaitError> {
+        self.wait_for_sensor_locked("ref_locked", timeout)
+    }
+
+    /// Wait for every sensor in [`sensor_names`](Self::sensor_names) whose
+    /// name ends in `_locked` to report locked.
+    ///
+    /// This is a convenience wrapper around
+    /// [`wait_for_sensor_locked`](Self::wait_for_sensor_locked) for the
+    /// common case of waiting on all of a channel's lock-detect sensors at
+    /// once, e.g. as a settling barrier after retuning.
+    pub fn wait_for_locks(&self, timeout: Duration) -> Result<(), LockWaitError> {
+        let deadline = Instant::now() + timeout;
+        for name in self.sensor_names()? {
+            if !name.ends_with("_locked") {
+                continue;
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            self.wait_for_sensor_locked(&name, remaining)?;
+        }
+        Ok(())
+    }
+
+    /// Get the currently applied manual DC offset correction, as last set
+    /// by [`ChannelConfigurationBuilder::set_dc_offset`](super::ChannelConfigurationBuilder::set_dc_offset),
+    /// so a calibration workflow can read it back to store or restore it.
+    pub fn dc_offset(&self) -> Result<Complex<f64>> {
+        let mut offset = uhd_usrp_sys::uhd_complex_double_t {
+            real: 0.0,
+            imag: 0.0,
+        };
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_dc_offset,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_dc_offset,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                self.channel,
+                addr_of_mut!(offset),
+            )
+        })?;
+        Ok(Complex::new(offset.real, offset.imag))
+    }
+
+    /// Get the currently applied manual IQ imbalance correction, as last
+    /// set by [`ChannelConfigurationBuilder::set_iq_balance`](super::ChannelConfigurationBuilder::set_iq_balance).
+    pub fn iq_balance(&self) -> Result<Complex<f64>> {
+        let mut correction = uhd_usrp_sys::uhd_complex_double_t {
+            real: 0.0,
+            imag: 0.0,
+        };
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_iq_balance,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_iq_balance,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                self.channel,
+                addr_of_mut!(correction),
+            )
+        })?;
+        Ok(Complex::new(correction.real, correction.imag))
+    }
+
+    /// Get a list of the names of filters in this channel's analog/digital
+    /// filter chain.
+    pub fn filter_names(&self) -> Result<Vec<String>> {
+        let mut names = FfiStringVec::new();
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_filter_names,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_filter_names,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                self.channel,
+                names.as_mut_ptr(),
+            )
+        })?;
+        Ok(names.to_vec())
+    }
+
+    /// Get a filter from this channel's filter chain by name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the filter name cannot be represented as a valid C string.
+    pub fn filter(&self, name: &str) -> Result<Filter> {
+        let name = CString::new(name).expect("invalid characters in filter name");
+        let handle = OwnedHandle::<uhd_usrp_sys::uhd_filter_info_base_t>::new(
+            uhd_usrp_sys::uhd_filter_info_base_make,
+            uhd_usrp_sys::uhd_filter_info_base_free,
+        )?;
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_filter,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_filter,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                name.as_ptr(),
+                self.channel,
+                handle.as_mut_mut_ptr(),
+            )
+        })?;
+        Ok(Filter::from_handle(handle))
+    }
+
     /// Get the name of the frontend.
     pub fn subdev_name(&self) -> Result<String> {
         let mut name = FfiString::with_capacity(64);
@@ -476,6 +777,43 @@ impl<'usrp, const D: usize> ChannelConfiguration<'usrp, D> {
         name.into_string()
     }
 
+    /// The subdev pair (daughterboard name + frontend name) that backs this
+    /// channel index, looked up from the device's current subdev spec.
+    ///
+    /// This lets MIMO setups deterministically map a `rx_channel`/`tx_channel`
+    /// index back to a physical port instead of guessing index order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UhdError::Index`] if the device's subdev spec has no pair
+    /// at this channel's index.
+    pub fn subdev_pair(&self) -> Result<SubdevPair> {
+        let spec = match D {
+            RX_DIR => self.usrp.rx_subdev_spec()?,
+            TX_DIR => self.usrp.tx_subdev_spec()?,
+            _ => unreachable!(),
+        };
+        spec.get(self.channel).ok_or(UhdError::Index)
+    }
+
+    /// Capture the channel's current frontend configuration as a
+    /// [`ChannelSettings`], suitable for saving as a preset or diffing
+    /// against a later read.
+    ///
+    /// `lo_source` and `lo_freq` are left as `None` if the device does not
+    /// support independently configurable LOs for this channel.
+    pub fn snapshot(&self) -> Result<ChannelSettings> {
+        Ok(ChannelSettings {
+            antenna: self.antenna()?,
+            center_freq: self.center_freq()?,
+            bandwidth: self.bandwidth()?,
+            gain: self.gain(None)?,
+            sample_rate: self.sample_rate()?,
+            lo_source: self.lo_source(None).ok(),
+            lo_freq: self.lo_freq(None).ok(),
+        })
+    }
+
     /// Convenience function to print common channel information.
     ///
     /// Info includes:
@@ -485,16 +823,7 @@ impl<'usrp, const D: usize> ChannelConfiguration<'usrp, D> {
     /// - Gain
     /// - Sample rate
     pub fn print_common(&self) -> Result<()> {
-        let antenna = self.antenna()?;
-        let freq = self.center_freq()?;
-        let bw = self.bandwidth()?;
-        let gain = self.gain(None)?;
-        let rate = self.sample_rate()?;
-        println!("Antenna: {}", antenna);
-        println!("Frequency: {} MHz", freq / 1e6);
-        println!("Bandwidth: {} MHz", bw / 1e6);
-        println!("Gain: {} dB", gain);
-        println!("Rate: {} Msps", rate / 1e6);
+        self.capabilities()?.print_common();
         Ok(())
     }
 }
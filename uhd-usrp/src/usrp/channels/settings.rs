@@ -0,0 +1,20 @@
+/// A snapshot of a channel's frontend configuration, suitable for saving as
+/// a radio preset or diffing against a live device.
+///
+/// Obtain one from [`ChannelConfiguration::snapshot`](super::ChannelConfiguration::snapshot)
+/// and replay it with [`ChannelConfigurationBuilder::apply`](super::ChannelConfigurationBuilder::apply).
+///
+/// The center frequency is captured as a plain value rather than a full
+/// [`TuneRequest`](crate::types::TuneRequest), since the latter carries a raw
+/// FFI handle that isn't meaningful to serialize.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelSettings {
+    pub antenna: String,
+    pub center_freq: f64,
+    pub bandwidth: f64,
+    pub gain: f64,
+    pub sample_rate: f64,
+    pub lo_source: Option<String>,
+    pub lo_freq: Option<f64>,
+}
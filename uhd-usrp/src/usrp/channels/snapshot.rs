@@ -0,0 +1,66 @@
+use super::ChannelConfiguration;
+use crate::{types::Range, usrp::HardwareInfo, Result};
+
+/// A full capability snapshot of a channel, combining its static capabilities
+/// (supported ranges, antennas, sensors, ...) with its current settings.
+///
+/// Obtain one from [`ChannelConfiguration::capabilities`]. Unlike
+/// [`ChannelSettings`](super::ChannelSettings), which only captures the
+/// handful of fields needed to replay a preset, this is meant for inspection
+/// and reporting, e.g. in place of hand-rolled `println!` calls.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelSnapshot {
+    pub antenna: String,
+    pub antennas: Vec<String>,
+    pub center_freq: f64,
+    pub center_freq_ranges: Vec<Range>,
+    pub bandwidth: f64,
+    pub bandwidth_ranges: Vec<Range>,
+    pub gain: f64,
+    pub gain_ranges: Vec<Range>,
+    pub sample_rate: f64,
+    pub sample_rates: Vec<Range>,
+    pub lo_names: Vec<String>,
+    pub sensor_names: Vec<String>,
+    pub subdev_name: String,
+    pub hardware_info: HardwareInfo,
+}
+
+impl<'usrp, const D: usize> ChannelConfiguration<'usrp, D> {
+    /// Capture a full capability snapshot of this channel.
+    ///
+    /// This queries every range, name list, and piece of hardware info the
+    /// channel exposes, so it's more expensive than a single accessor call;
+    /// prefer the individual methods when only one field is needed.
+    pub fn capabilities(&self) -> Result<ChannelSnapshot> {
+        Ok(ChannelSnapshot {
+            antenna: self.antenna()?,
+            antennas: self.antennas()?,
+            center_freq: self.center_freq()?,
+            center_freq_ranges: self.center_freq_ranges()?.ranges().to_vec(),
+            bandwidth: self.bandwidth()?,
+            bandwidth_ranges: self.bandwidth_ranges()?.ranges().to_vec(),
+            gain: self.gain(None)?,
+            gain_ranges: self.gain_ranges(None)?.ranges().to_vec(),
+            sample_rate: self.sample_rate()?,
+            sample_rates: self.sample_rates()?.ranges().to_vec(),
+            lo_names: self.lo_names()?,
+            sensor_names: self.sensor_names()?,
+            subdev_name: self.subdev_name()?,
+            hardware_info: self.hardware_info()?,
+        })
+    }
+}
+
+impl ChannelSnapshot {
+    /// Print the fields previously covered by the old ad-hoc
+    /// `ChannelConfiguration::print_common`.
+    pub fn print_common(&self) {
+        println!("Antenna: {}", self.antenna);
+        println!("Frequency: {} MHz", self.center_freq / 1e6);
+        println!("Bandwidth: {} MHz", self.bandwidth / 1e6);
+        println!("Gain: {} dB", self.gain);
+        println!("Rate: {} Msps", self.sample_rate / 1e6);
+    }
+}
@@ -1,11 +1,13 @@
 use std::{ffi::CString, ptr::addr_of_mut};
 
-use super::{RX_DIR, TX_DIR};
+use num_complex::Complex;
+
+use super::{ChannelSettings, RX_DIR, TX_DIR};
 use crate::{
     error::try_uhd,
-    types::{TuneRequest, TuneResult},
+    types::{Filter, LoTuningMode, MetaRange, TuneRequest, TuneResult},
     usrp::Usrp,
-    Result,
+    Result, UhdError,
 };
 
 pub struct ChannelConfigurationBuilder<'usrp, const D: usize> {
@@ -14,6 +16,10 @@ pub struct ChannelConfigurationBuilder<'usrp, const D: usize> {
 }
 
 impl<'usrp, const D: usize> ChannelConfigurationBuilder<'usrp, D> {
+    pub(crate) fn new(usrp: &'usrp Usrp, channel: usize) -> Self {
+        Self { usrp, channel }
+    }
+
     /// Select the antenna to use on the frontend.
     ///
     /// # Errors
@@ -33,33 +39,91 @@ impl<'usrp, const D: usize> ChannelConfigurationBuilder<'usrp, D> {
     /// Set the RX frontend's bandwidth in Hz.
     ///
     /// If a bandwidth is provided that is outside the valid range,
-    /// it is coerced to the nearest valid value.
+    /// it is coerced to the nearest valid value. Use
+    /// [`set_bandwidth_coerced`](Self::set_bandwidth_coerced) to find out
+    /// what value the device actually accepted.
     pub fn set_bandwidth(self, bw: f64) -> Result<Self> {
+        self.set_bandwidth_coerced(bw).map(|(this, _)| this)
+    }
+
+    /// Like [`set_bandwidth`](Self::set_bandwidth), but also returns the
+    /// actual bandwidth the device coerced the request to.
+    pub fn set_bandwidth_coerced(self, bw: f64) -> Result<(Self, f64)> {
         let f = match D {
             RX_DIR => uhd_usrp_sys::uhd_usrp_set_rx_bandwidth,
             TX_DIR => uhd_usrp_sys::uhd_usrp_set_tx_bandwidth,
             _ => unreachable!(),
         };
         try_uhd!(unsafe { f(self.usrp.handle().as_mut_ptr(), bw, self.channel) })?;
-        Ok(self)
+
+        let mut actual = std::mem::MaybeUninit::uninit();
+        let g = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_bandwidth,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_bandwidth,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe { g(self.usrp.handle().as_mut_ptr(), self.channel, actual.as_mut_ptr()) })?;
+        Ok((self, unsafe { actual.assume_init() }))
     }
 
     /// Set the RX center frequency in Hz.
     ///
     /// If the requested frequency is outside of the valid frequency range,
-    /// it will be coerced to the nearest valid frequency.
+    /// it will be coerced to the nearest valid frequency. Use
+    /// [`set_center_freq_coerced`](Self::set_center_freq_coerced) to get
+    /// back the actual frequencies the device settled on.
     pub fn set_center_freq(self, freq: f64) -> Result<Self> {
         self.tune(&TuneRequest::new(freq).rf_freq_auto().dsp_freq_auto())
     }
 
+    /// Like [`set_center_freq`](Self::set_center_freq), but also returns
+    /// the populated [`TuneResult`].
+    pub fn set_center_freq_coerced(self, freq: f64) -> Result<(Self, TuneResult)> {
+        self.tune_coerced(&TuneRequest::new(freq).rf_freq_auto().dsp_freq_auto())
+    }
+
+    /// Tune to `target_freq`, deliberately placing the RF LO `lo_offset` Hz
+    /// away and shifting the DSP back to compensate.
+    ///
+    /// Moving the LO off the target frequency like this pushes LO
+    /// leakage and any DC offset in the analog front end out of the band
+    /// of interest, at the cost of needing `lo_offset` extra bandwidth
+    /// either side of the target for the DSP to shift back. Uses
+    /// fractional-N tuning; see
+    /// [`set_center_freq_with_lo_offset_and_mode`](Self::set_center_freq_with_lo_offset_and_mode)
+    /// to select integer-N tuning instead.
+    pub fn set_center_freq_with_lo_offset(self, target_freq: f64, lo_offset: f64) -> Result<Self> {
+        self.set_center_freq_with_lo_offset_and_mode(target_freq, lo_offset, LoTuningMode::Fractional)
+    }
+
+    /// Like [`set_center_freq_with_lo_offset`](Self::set_center_freq_with_lo_offset),
+    /// but also selects the RF synthesizer's tuning mode.
+    pub fn set_center_freq_with_lo_offset_and_mode(
+        self,
+        target_freq: f64,
+        lo_offset: f64,
+        mode: LoTuningMode,
+    ) -> Result<Self> {
+        let req = TuneRequest::with_lo_offset(target_freq, lo_offset).with_lo_tuning_mode(mode);
+        self.tune(&req)
+    }
+
     /// Set the RX gain value in dB for the specified gain element.
     ///
     /// If the requested gain value is outside the valid range,
-    /// it will be coerced to a valid gain value.
+    /// it will be coerced to a valid gain value. Use
+    /// [`set_gain_coerced`](Self::set_gain_coerced) to find out what gain
+    /// the device actually accepted.
     ///
     /// The name of the gain element to set can be provided.
     /// If `None`, it is distributed across all gain elements.
     pub fn set_gain(self, name: Option<&str>, gain: f64) -> Result<Self> {
+        self.set_gain_coerced(name, gain).map(|(this, _)| this)
+    }
+
+    /// Like [`set_gain`](Self::set_gain), but also returns the actual gain
+    /// the device coerced the request to.
+    pub fn set_gain_coerced(self, name: Option<&str>, gain: f64) -> Result<(Self, f64)> {
         let name = CString::new(name.unwrap_or("")).unwrap();
         let f = match D {
             RX_DIR => uhd_usrp_sys::uhd_usrp_set_rx_gain,
@@ -74,6 +138,142 @@ impl<'usrp, const D: usize> ChannelConfigurationBuilder<'usrp, D> {
                 name.as_ptr(),
             )
         })?;
+
+        let mut actual = std::mem::MaybeUninit::uninit();
+        let g = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_gain,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_gain,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            g(
+                self.usrp.handle().as_mut_ptr(),
+                self.channel,
+                name.as_ptr(),
+                actual.as_mut_ptr(),
+            )
+        })?;
+        Ok((self, unsafe { actual.assume_init() }))
+    }
+
+    /// Returns true if the device has a power reference for this channel,
+    /// i.e. whether [`set_power_reference`](Self::set_power_reference) is
+    /// supported.
+    pub fn has_power_reference(&self) -> Result<bool> {
+        let mut result = false;
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_has_rx_power_reference,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_has_tx_power_reference,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                self.channel,
+                addr_of_mut!(result),
+            )
+        })?;
+        Ok(result)
+    }
+
+    /// Get the valid range of calibrated power reference levels, in dBm.
+    pub fn power_range(&self) -> Result<MetaRange> {
+        let handle = crate::ffi::OwnedHandle::<uhd_usrp_sys::uhd_meta_range_t>::new(
+            uhd_usrp_sys::uhd_meta_range_make,
+            uhd_usrp_sys::uhd_meta_range_free,
+        )?;
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_power_range,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_power_range,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                self.channel,
+                handle.as_mut_ptr(),
+            )
+        })?;
+        MetaRange::from_handle(handle)
+    }
+
+    /// Request an absolute, calibrated power level at the antenna connector,
+    /// in dBm, rather than a device-relative gain in dB.
+    ///
+    /// UHD uses the device's stored power calibration table to pick the
+    /// gain setting that achieves the requested level, coercing the value
+    /// into [`power_range`](Self::power_range) if it falls outside it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UhdError::NotImplemented`] if the device has no power
+    /// calibration data for this channel; check
+    /// [`has_power_reference`](Self::has_power_reference) first.
+    pub fn set_power_reference(self, dbm: f64) -> Result<Self> {
+        self.set_power_reference_coerced(dbm).map(|(this, _)| this)
+    }
+
+    /// Like [`set_power_reference`](Self::set_power_reference), but also
+    /// returns the actual power level the device coerced the request to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UhdError::NotImplemented`] if the device has no power
+    /// calibration data for this channel; check
+    /// [`has_power_reference`](Self::has_power_reference) first.
+    pub fn set_power_reference_coerced(self, dbm: f64) -> Result<(Self, f64)> {
+        if !self.has_power_reference()? {
+            return Err(UhdError::NotImplemented);
+        }
+        let (set, get) = match D {
+            RX_DIR => (
+                uhd_usrp_sys::uhd_usrp_set_rx_power_reference,
+                uhd_usrp_sys::uhd_usrp_get_rx_power_reference,
+            ),
+            TX_DIR => (
+                uhd_usrp_sys::uhd_usrp_set_tx_power_reference,
+                uhd_usrp_sys::uhd_usrp_get_tx_power_reference,
+            ),
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe { set(self.usrp.handle().as_mut_ptr(), dbm, self.channel) })?;
+        let mut actual = std::mem::MaybeUninit::uninit();
+        try_uhd!(unsafe {
+            get(
+                self.usrp.handle().as_mut_ptr(),
+                self.channel,
+                actual.as_mut_ptr(),
+            )
+        })?;
+        Ok((self, unsafe { actual.assume_init() }))
+    }
+
+    /// Set the source of the LO used by the channel.
+    ///
+    /// Typical values are `"internal"` and `"external"`, although some
+    /// devices (e.g. the TwinRX) offer more options, such as `"companion"`.
+    /// See [`ChannelConfiguration::lo_sources`](super::ChannelConfiguration::lo_sources)
+    /// for the list of values this device accepts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the LO name or source is not valid.
+    pub fn set_lo_source(self, name: Option<&str>, source: &str) -> Result<Self> {
+        let name = CString::new(name.unwrap_or("")).unwrap();
+        let source = CString::new(source).unwrap();
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_set_rx_lo_source,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_set_tx_lo_source,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                source.as_ptr(),
+                name.as_ptr(),
+                self.channel,
+            )
+        })?;
         Ok(self)
     }
 
@@ -97,6 +297,12 @@ impl<'usrp, const D: usize> ChannelConfigurationBuilder<'usrp, D> {
     ///
     /// Returns an error if the LO name is not valid.
     pub fn set_lo_freq(self, name: Option<&str>, freq: f64) -> Result<Self> {
+        self.set_lo_freq_coerced(name, freq).map(|(this, _)| this)
+    }
+
+    /// Like [`set_lo_freq`](Self::set_lo_freq), but also returns the actual
+    /// LO frequency the device coerced the request to.
+    pub fn set_lo_freq_coerced(self, name: Option<&str>, freq: f64) -> Result<(Self, f64)> {
         let name = CString::new(name.unwrap_or("")).unwrap();
         let mut result = 0.0;
         let f = match D {
@@ -113,6 +319,59 @@ impl<'usrp, const D: usize> ChannelConfigurationBuilder<'usrp, D> {
                 addr_of_mut!(result),
             )
         })?;
+        Ok((self, result))
+    }
+
+    /// Set whether the LO used by the device is exported
+    ///
+    /// For USRPs that support exportable LOs, this function configures
+    /// if the LO used by the channel is exported or not.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if LO exporting is not available or if the
+    /// given name is invalid.
+    pub fn set_lo_export_enabled(self, name: Option<&str>, en: bool) -> Result<Self> {
+        let name = CString::new(name.unwrap_or("")).unwrap();
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_set_rx_lo_export_enabled,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_set_tx_lo_export_enabled,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                en,
+                name.as_ptr(),
+                self.channel,
+            )
+        })?;
+        Ok(self)
+    }
+
+    /// Apply a filter to a named stage in this channel's analog/digital
+    /// filter chain, e.g. a [`Filter`] read via
+    /// [`ChannelConfiguration::filter`](super::ChannelConfiguration::filter)
+    /// with [`Filter::set_bypass`] toggled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the filter name cannot be represented as a valid C string.
+    pub fn set_filter(self, name: &str, filter: &Filter) -> Result<Self> {
+        let name = CString::new(name).expect("invalid characters in filter name");
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_set_rx_filter,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_set_tx_filter,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe {
+            f(
+                self.usrp.handle().as_mut_ptr(),
+                name.as_ptr(),
+                filter.handle().as_mut_ptr(),
+                self.channel,
+            )
+        })?;
         Ok(self)
     }
 
@@ -144,12 +403,24 @@ impl<'usrp, const D: usize> ChannelConfigurationBuilder<'usrp, D> {
     /// Set the RX sample rate in samples per second.
     ///
     /// This function will coerce the requested rate to a rate that the
-    /// device can handle. A warning may be logged during coercion.
+    /// device can handle. A warning may be logged during coercion. Use
+    /// [`set_sample_rate_coerced`](Self::set_sample_rate_coerced) to find
+    /// out what rate the device actually accepted.
     ///
     /// # Panics
     ///
     /// Panics if the given rate is non-positive.
     pub fn set_sample_rate(self, rate: f64) -> Result<Self> {
+        self.set_sample_rate_coerced(rate).map(|(this, _)| this)
+    }
+
+    /// Like [`set_sample_rate`](Self::set_sample_rate), but also returns
+    /// the actual sample rate the device coerced the request to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given rate is non-positive.
+    pub fn set_sample_rate_coerced(self, rate: f64) -> Result<(Self, f64)> {
         if rate <= 0.0 {
             panic!("sample rate must be positive");
         }
@@ -159,14 +430,31 @@ impl<'usrp, const D: usize> ChannelConfigurationBuilder<'usrp, D> {
             _ => unreachable!(),
         };
         try_uhd!(unsafe { f(self.usrp.handle().as_mut_ptr(), rate, self.channel) })?;
-        Ok(self)
+
+        let mut actual = std::mem::MaybeUninit::uninit();
+        let g = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_get_rx_rate,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_get_tx_rate,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe { g(self.usrp.handle().as_mut_ptr(), self.channel, actual.as_mut_ptr()) })?;
+        Ok((self, unsafe { actual.assume_init() }))
     }
 
     /// Set the tuning parameters for the channel.
     ///
-    /// This function allows setting more advanced parameters.
+    /// This function allows setting more advanced parameters. Use
+    /// [`tune_coerced`](Self::tune_coerced) to get back the populated
+    /// [`TuneResult`] instead of discarding it.
     pub fn tune(self, req: &TuneRequest) -> Result<Self> {
-        let req = req.inner();
+        let (this, _) = self.tune_coerced(req)?;
+        Ok(this)
+    }
+
+    /// Like [`tune`](Self::tune), but also returns the [`TuneResult`]
+    /// reporting the actual frequencies the device settled on.
+    pub fn tune_coerced(self, req: &TuneRequest) -> Result<(Self, TuneResult)> {
+        let inner = req.inner();
         let mut result = TuneResult::default();
         let f = match D {
             RX_DIR => uhd_usrp_sys::uhd_usrp_set_rx_freq,
@@ -176,26 +464,90 @@ impl<'usrp, const D: usize> ChannelConfigurationBuilder<'usrp, D> {
         try_uhd!(unsafe {
             f(
                 self.usrp.handle().as_mut_ptr(),
-                req as *const _ as *mut _,
+                inner as *const _ as *mut _,
                 self.channel,
                 result.inner_mut(),
             )
         })?;
+        Ok((self, result))
+    }
+
+    /// Manually set the DC offset correction, overriding the value
+    /// computed by the automatic DC offset correction (if enabled).
+    ///
+    /// Per UHD semantics, setting a manual value halts the automatic
+    /// averaging loop started by `set_dc_offset_enabled`; re-enable it to
+    /// resume tracking automatically.
+    pub fn set_dc_offset(self, offset: Complex<f64>) -> Result<Self> {
+        let offset = uhd_usrp_sys::uhd_complex_double_t {
+            real: offset.re,
+            imag: offset.im,
+        };
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_set_rx_dc_offset,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_set_tx_dc_offset,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe { f(self.usrp.handle().as_mut_ptr(), offset, self.channel) })?;
         Ok(self)
     }
-}
 
-impl<'a> ChannelConfigurationBuilder<'a, TX_DIR> {
-    pub(crate) fn new(usrp: &'a Usrp, channel: usize) -> Self {
-        Self { usrp, channel }
+    /// Manually set the IQ imbalance correction, overriding the value
+    /// computed by the automatic IQ balance correction (if enabled).
+    ///
+    /// Per UHD semantics, setting a manual value halts the automatic
+    /// correction loop started by `set_iq_balance_enabled`; re-enable it to
+    /// resume tracking automatically.
+    pub fn set_iq_balance(self, correction: Complex<f64>) -> Result<Self> {
+        let correction = uhd_usrp_sys::uhd_complex_double_t {
+            real: correction.re,
+            imag: correction.im,
+        };
+        let f = match D {
+            RX_DIR => uhd_usrp_sys::uhd_usrp_set_rx_iq_balance,
+            TX_DIR => uhd_usrp_sys::uhd_usrp_set_tx_iq_balance,
+            _ => unreachable!(),
+        };
+        try_uhd!(unsafe { f(self.usrp.handle().as_mut_ptr(), correction, self.channel) })?;
+        Ok(self)
     }
-}
 
-impl<'a> ChannelConfigurationBuilder<'a, RX_DIR> {
-    pub(crate) fn new(usrp: &'a Usrp, channel: usize) -> Self {
-        Self { usrp, channel }
+    /// Manually set the DC offset correction from an `(i, q)` pair, rather
+    /// than a raw [`Complex`] value.
+    ///
+    /// `1.0` means full-scale, matching UHD's convention.
+    pub fn set_dc_offset_iq(self, i: f64, q: f64) -> Result<Self> {
+        self.set_dc_offset(Complex::new(i, q))
     }
 
+    /// Manually set the IQ imbalance correction from a magnitude/phase
+    /// pair, rather than a raw complex value.
+    pub fn set_iq_balance_mag_phase(self, magnitude: f64, phase: f64) -> Result<Self> {
+        self.set_iq_balance(Complex::from_polar(magnitude, phase))
+    }
+
+    /// Replay a previously captured [`ChannelSettings`] onto this channel.
+    ///
+    /// Settings are applied in dependency order: sample rate and bandwidth
+    /// first, then LO source (if present) before LO frequency, then the
+    /// center frequency and gain.
+    pub fn apply(self, settings: &ChannelSettings) -> Result<Self> {
+        let mut this = self
+            .set_sample_rate(settings.sample_rate)?
+            .set_bandwidth(settings.bandwidth)?;
+        if let Some(source) = &settings.lo_source {
+            this = this.set_lo_source(None, source)?;
+        }
+        if let Some(freq) = settings.lo_freq {
+            this = this.set_lo_freq(None, freq)?;
+        }
+        this.set_center_freq(settings.center_freq)?
+            .set_antenna(&settings.antenna)?
+            .set_gain(None, settings.gain)
+    }
+}
+
+impl<'a> ChannelConfigurationBuilder<'a, RX_DIR> {
     /// Enable or disable the RX AGC module.
     ///
     /// Once this module is enabled manual gain settings will be ignored.
@@ -245,25 +597,4 @@ impl<'a> ChannelConfigurationBuilder<'a, RX_DIR> {
         Ok(self)
     }
 
-    /// Set whether the LO used by the device is exported
-    ///
-    /// For USRPs that support exportable LOs, this function configures
-    /// if the LO used by the channel is exported or not.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if LO exporting is not available or if the
-    /// given name is invalid.
-    pub fn set_lo_export_enabled(self, name: Option<&str>, en: bool) -> Result<Self> {
-        let name = CString::new(name.unwrap_or("")).unwrap();
-        try_uhd!(unsafe {
-            uhd_usrp_sys::uhd_usrp_set_rx_lo_export_enabled(
-                self.usrp.handle().as_mut_ptr(),
-                en,
-                name.as_ptr(),
-                self.channel,
-            )
-        })?;
-        Ok(self)
-    }
 }
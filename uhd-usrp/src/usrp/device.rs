@@ -3,14 +3,19 @@ use std::{ffi::CString, marker::PhantomData, ptr::addr_of_mut};
 use crate::{
     error::try_uhd,
     ffi::OwnedHandle,
-    stream::{RxStreamBuilder, TxStreamBuilder},
+    stream::{CpuFormat, DynRxStreamBuilder, DynTxStreamBuilder, RxStreamBuilder, TxStreamBuilder},
     types::DeviceArgs,
     Result, Sample, TimeSpec,
 };
 
 use super::{
-    channels::{ChannelConfiguration, ChannelConfigurationBuilder, RX_DIR, TX_DIR},
+    channels::{
+        Channel, ChannelConfiguration, ChannelConfigurationBuilder, ChannelGroupBuilder, RX_DIR,
+        TX_DIR,
+    },
+    fpga::FpgaImageLoader,
     mboard::Motherboard,
+    subdev_spec::SubdevSpec,
 };
 
 /// The entry point for interacting with a connected USRP.
@@ -77,6 +82,13 @@ pub struct Usrp {
     _unsync: PhantomData<std::cell::Cell<()>>,
 }
 
+// The underlying `uhd_usrp_handle` can be owned by a single thread at a
+// time and handed off between threads (e.g. moved onto a background
+// polling thread, as `SensorMonitor` does), but UHD does not support
+// concurrent API calls against the same handle from multiple threads, so
+// `Usrp` stays `!Sync` via `_unsync` above.
+unsafe impl Send for Usrp {}
+
 impl Usrp {
     /// Attempts to open a USRP using the given [`DeviceArgs`].
     ///
@@ -156,6 +168,21 @@ impl Usrp {
         Motherboard::new(self, mboard)
     }
 
+    /// Returns a loader for querying and flashing the FPGA image on this
+    /// USRP's motherboard(s).
+    #[must_use]
+    pub fn fpga_image_loader(&self) -> FpgaImageLoader {
+        FpgaImageLoader::new(self)
+    }
+
+    /// Attempt to recover `mboard` after a failed FPGA image load, e.g. by
+    /// reflashing the device's safe/golden image.
+    pub fn recover_mboard(&mut self, mboard: usize) -> Result<()> {
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_usrp_recover_mboard(self.handle.as_mut_ptr(), mboard)
+        })
+    }
+
     /// Get the number of connected motherboards.
     pub fn n_mboards(&self) -> Result<usize> {
         let mut mboards = 0;
@@ -198,6 +225,164 @@ impl Usrp {
         })?;
         Ok(())
     }
+
+    /// Apply a common master clock rate to every motherboard, then
+    /// synchronize their time registers to `time` on the next PPS edge.
+    ///
+    /// This is a convenience wrapper for multi-channel (MIMO) setups: all
+    /// connected motherboards must share the same master clock rate for
+    /// their sample clocks to stay phase-aligned, and [`set_time_unknown_pps`](Self::set_time_unknown_pps)
+    /// is used to latch the same time value across all of their timekeepers.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use uhd_usrp::{timespec, Usrp};
+    ///
+    /// let mut usrp = Usrp::open_any().expect("failed to open USRP");
+    /// usrp.sync_clocks(200e6, timespec!(0)).expect("failed to sync clocks");
+    /// ```
+    pub fn sync_clocks(&mut self, master_clock_rate: f64, time: TimeSpec) -> Result<()> {
+        for mboard in 0..self.n_mboards()? {
+            self.mboard(mboard).set_master_clock_rate(master_clock_rate)?;
+        }
+        self.set_time_unknown_pps(time)
+    }
+
+    /// Latch a command time on one channel, or every channel if `channel`
+    /// is `None`.
+    ///
+    /// Once set, subsequent [`ChannelConfiguration`](super::ChannelConfiguration)/
+    /// [`ChannelConfigurationBuilder`](super::ChannelConfigurationBuilder) calls (tuning,
+    /// gain, antenna selection, ...) for the affected channel(s) are not
+    /// applied immediately; the device latches them and executes them at
+    /// `time` instead. This enables glitch-free, timestamp-aligned
+    /// frequency hops and gain changes across channels, instead of relying
+    /// on software timing. Clear it with [`clear_command_time`](Self::clear_command_time)
+    /// once the scheduled change has been queued, or use
+    /// [`with_command_time`](Self::with_command_time) to do both
+    /// automatically.
+    pub fn set_command_time(&mut self, time: TimeSpec, channel: Option<Channel>) -> Result<()> {
+        let set_rx = |channel| {
+            try_uhd!(unsafe {
+                uhd_usrp_sys::uhd_usrp_set_rx_command_time(
+                    self.handle.as_mut_ptr(),
+                    time.full_secs(),
+                    time.frac_secs(),
+                    channel,
+                )
+            })
+        };
+        let set_tx = |channel| {
+            try_uhd!(unsafe {
+                uhd_usrp_sys::uhd_usrp_set_tx_command_time(
+                    self.handle.as_mut_ptr(),
+                    time.full_secs(),
+                    time.frac_secs(),
+                    channel,
+                )
+            })
+        };
+        match channel {
+            Some(Channel::Rx(i)) => set_rx(i),
+            Some(Channel::Tx(i)) => set_tx(i),
+            None => {
+                for i in 0..self.rx_channels()? {
+                    set_rx(i)?;
+                }
+                for i in 0..self.tx_channels()? {
+                    set_tx(i)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Clear a previously latched command time on one channel, or every
+    /// channel if `channel` is `None`. See [`set_command_time`](Self::set_command_time).
+    pub fn clear_command_time(&mut self, channel: Option<Channel>) -> Result<()> {
+        let clear_rx = |channel| {
+            try_uhd!(unsafe {
+                uhd_usrp_sys::uhd_usrp_clear_rx_command_time(self.handle.as_mut_ptr(), channel)
+            })
+        };
+        let clear_tx = |channel| {
+            try_uhd!(unsafe {
+                uhd_usrp_sys::uhd_usrp_clear_tx_command_time(self.handle.as_mut_ptr(), channel)
+            })
+        };
+        match channel {
+            Some(Channel::Rx(i)) => clear_rx(i),
+            Some(Channel::Tx(i)) => clear_tx(i),
+            None => {
+                for i in 0..self.rx_channels()? {
+                    clear_rx(i)?;
+                }
+                for i in 0..self.tx_channels()? {
+                    clear_tx(i)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Run `f` with a command time latched on `channel` (or every channel
+    /// if `None`), clearing it again once `f` returns (whether or not it
+    /// succeeded).
+    ///
+    /// This is the scoped-guard form of [`set_command_time`](Self::set_command_time):
+    /// use it to schedule a tune/gain/antenna change for a specific device
+    /// time without having to remember to call
+    /// [`clear_command_time`](Self::clear_command_time) yourself.
+    pub fn with_command_time<T>(
+        &mut self,
+        time: TimeSpec,
+        channel: Option<Channel>,
+        f: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        self.set_command_time(time, channel)?;
+        let result = f(self);
+        self.clear_command_time(channel)?;
+        result
+    }
+
+    /// Set the Rx frontend specification on every motherboard.
+    ///
+    /// A [`SubdevSpec`] declares which physical daughterboard/frontend
+    /// slot backs each logical Rx channel, e.g. a spec parsed from
+    /// `"A:A A:B"` maps channel 0 to the left frontend and channel 1 to
+    /// the right frontend on a B210-style device. Set this before
+    /// configuring per-channel antenna/gain/frequency via [`set_rx_config`](Self::set_rx_config).
+    ///
+    /// For multi-motherboard configurations where each board needs a
+    /// different spec, use [`mboard`](Self::mboard) and
+    /// [`Motherboard::set_rx_subdev`] directly.
+    pub fn set_rx_subdev_spec(&mut self, spec: &SubdevSpec) -> Result<()> {
+        for mboard in 0..self.n_mboards()? {
+            self.mboard(mboard).set_rx_subdev(spec)?;
+        }
+        Ok(())
+    }
+
+    /// Set the Tx frontend specification on every motherboard.
+    ///
+    /// See [`set_rx_subdev_spec`](Self::set_rx_subdev_spec) for the Rx-side equivalent.
+    pub fn set_tx_subdev_spec(&mut self, spec: &SubdevSpec) -> Result<()> {
+        for mboard in 0..self.n_mboards()? {
+            self.mboard(mboard).set_tx_subdev(spec)?;
+        }
+        Ok(())
+    }
+
+    /// Get the Rx frontend specification currently in use on the first motherboard.
+    pub fn rx_subdev_spec(&self) -> Result<SubdevSpec> {
+        self.mboard(0).rx_subdev_spec()
+    }
+
+    /// Get the Tx frontend specification currently in use on the first motherboard.
+    pub fn tx_subdev_spec(&self) -> Result<SubdevSpec> {
+        self.mboard(0).tx_subdev_spec()
+    }
 }
 
 /// RX and TX streaming.
@@ -271,6 +456,22 @@ impl Usrp {
     pub fn tx_stream<T: Sample>(&self) -> TxStreamBuilder<T> {
         TxStreamBuilder::new(self)
     }
+
+    /// Returns a builder for opening an RX stream whose CPU format is
+    /// chosen at runtime via `cpu_format` rather than a [`Sample`] type
+    /// parameter. See [`DynRxStream`](crate::stream::DynRxStream) for why this is useful.
+    #[must_use]
+    pub fn dyn_rx_stream(&self, cpu_format: CpuFormat) -> DynRxStreamBuilder {
+        DynRxStreamBuilder::new(self, cpu_format)
+    }
+
+    /// Returns a builder for opening a TX stream whose CPU format is
+    /// chosen at runtime via `cpu_format` rather than a [`Sample`] type
+    /// parameter. See [`DynTxStream`](crate::stream::DynTxStream) for why this is useful.
+    #[must_use]
+    pub fn dyn_tx_stream(&self, cpu_format: CpuFormat) -> DynTxStreamBuilder {
+        DynTxStreamBuilder::new(self, cpu_format)
+    }
 }
 
 /// RX and TX configuration getters and setters.
@@ -366,4 +567,112 @@ impl Usrp {
     ) -> ChannelConfigurationBuilder<'a, { TX_DIR }> {
         ChannelConfigurationBuilder::<'a, TX_DIR>::new(self, channel)
     }
+
+    /// Write settings to several RX channels at once, for MIMO setups where
+    /// multiple channels need to share a sample rate, bandwidth, or LO.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use uhd_usrp::Usrp;
+    ///
+    /// let mut usrp = Usrp::open_any().expect("failed to open USRP");
+    /// usrp.set_rx_channel_group(&[0, 1])
+    ///     .set_sample_rate(4e6)
+    ///     .expect("failed to set sample rate");
+    /// ```
+    #[must_use]
+    pub fn set_rx_channel_group<'a>(
+        &'a mut self,
+        channels: &[usize],
+    ) -> ChannelGroupBuilder<'a, { RX_DIR }> {
+        ChannelGroupBuilder::<'a, RX_DIR>::new(self, channels)
+    }
+
+    /// Write settings to several TX channels at once, for MIMO setups where
+    /// multiple channels need to share a sample rate, bandwidth, or LO.
+    #[must_use]
+    pub fn set_tx_channel_group<'a>(
+        &'a mut self,
+        channels: &[usize],
+    ) -> ChannelGroupBuilder<'a, { TX_DIR }> {
+        ChannelGroupBuilder::<'a, TX_DIR>::new(self, channels)
+    }
+
+    /// Designate `exporter` as the RX LO source for `lo_name` and configure
+    /// each channel in `importers` to share it, for phase-coherent
+    /// multichannel setups (e.g. TwinRX's `"LO1"`/`"LO2"` stages).
+    ///
+    /// `exporter` is set to export `"internal"`; each channel in
+    /// `importers` is set to `"companion"` and its `lo_name` stage is tuned
+    /// to the exporter's actual LO frequency. Returns the actual per-channel
+    /// frequency, exporter first then each importer in the order given, so
+    /// callers can confirm lock.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UhdError::Key`] if `lo_name` isn't among `lo_names()` for
+    /// `exporter` or any channel in `importers`.
+    pub fn share_rx_lo(
+        &mut self,
+        exporter: usize,
+        importers: &[usize],
+        lo_name: &str,
+    ) -> Result<Vec<(usize, f64)>> {
+        share_lo::<RX_DIR>(self, exporter, importers, lo_name)
+    }
+
+    /// Designate `exporter` as the TX LO source for `lo_name` and configure
+    /// each channel in `importers` to share it. See
+    /// [`share_rx_lo`](Self::share_rx_lo) for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UhdError::Key`] if `lo_name` isn't among `lo_names()` for
+    /// `exporter` or any channel in `importers`.
+    pub fn share_tx_lo(
+        &mut self,
+        exporter: usize,
+        importers: &[usize],
+        lo_name: &str,
+    ) -> Result<Vec<(usize, f64)>> {
+        share_lo::<TX_DIR>(self, exporter, importers, lo_name)
+    }
+}
+
+/// Shared implementation backing [`Usrp::share_rx_lo`]/[`Usrp::share_tx_lo`].
+fn share_lo<const D: usize>(
+    usrp: &mut Usrp,
+    exporter: usize,
+    importers: &[usize],
+    lo_name: &str,
+) -> Result<Vec<(usize, f64)>> {
+    let check_stage = |channel: usize, source: &str| -> Result<()> {
+        let config = ChannelConfiguration::<'_, D>::new(usrp, channel);
+        if !config.lo_names()?.iter().any(|n| n == lo_name) {
+            return Err(crate::UhdError::Key);
+        }
+        if !config.lo_sources(Some(lo_name))?.iter().any(|s| s == source) {
+            return Err(crate::UhdError::Key);
+        }
+        Ok(())
+    };
+    check_stage(exporter, "internal")?;
+    for &channel in importers {
+        check_stage(channel, "companion")?;
+    }
+
+    ChannelConfigurationBuilder::<'_, D>::new(usrp, exporter)
+        .set_lo_source(Some(lo_name), "internal")?
+        .set_lo_export_enabled(Some(lo_name), true)?;
+    let exported_freq = ChannelConfiguration::<'_, D>::new(usrp, exporter).lo_freq(Some(lo_name))?;
+
+    let mut results = vec![(exporter, exported_freq)];
+    for &channel in importers {
+        let (_, actual) = ChannelConfigurationBuilder::<'_, D>::new(usrp, channel)
+            .set_lo_source(Some(lo_name), "companion")?
+            .set_lo_freq_coerced(Some(lo_name), exported_freq)?;
+        results.push((channel, actual));
+    }
+    Ok(results)
 }
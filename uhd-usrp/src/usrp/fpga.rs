@@ -0,0 +1,65 @@
+use std::ffi::CString;
+
+use crate::{
+    error::try_uhd,
+    ffi::FfiString,
+    Result,
+};
+
+use super::Usrp;
+
+/// Queries and loads FPGA images on a connected USRP's motherboard(s).
+///
+/// Obtained via [`Usrp::fpga_image_loader`]. Mirrors what the
+/// `uhd_image_loader` CLI does, so field upgrades of X3x0/N3xx devices can
+/// verify the loaded image matches the installed UHD ABI and reload it
+/// in-process if not.
+pub struct FpgaImageLoader<'a> {
+    usrp: &'a Usrp,
+}
+
+impl<'a> FpgaImageLoader<'a> {
+    pub(crate) fn new(usrp: &'a Usrp) -> Self {
+        Self { usrp }
+    }
+
+    /// Get the identifier of the FPGA image currently loaded on `mboard`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image identifier could not be retrieved,
+    /// or if the returned string is not valid UTF-8.
+    pub fn image_id(&self, mboard: usize) -> Result<String> {
+        let mut id = FfiString::with_capacity(32);
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_usrp_get_fpga_image_id(
+                self.usrp.handle().as_mut_ptr(),
+                mboard,
+                id.as_mut_ptr().cast(),
+                id.max_chars(),
+            )
+        })?;
+        id.into_string()
+    }
+
+    /// Load a `.bit`/`.bin` FPGA image file onto `mboard`.
+    ///
+    /// This blocks until the image has been written and the device has
+    /// re-enumerated. Callers should verify [`image_id`](Self::image_id)
+    /// afterwards, and fall back to [`Usrp::recover_mboard`] if the load
+    /// failed partway through.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` contains a null byte.
+    pub fn load_image(&self, path: &str, mboard: usize) -> Result<()> {
+        let path = CString::new(path).expect("path cannot contain null bytes");
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_usrp_load_fpga_image(
+                self.usrp.handle().as_mut_ptr(),
+                path.as_ptr(),
+                mboard,
+            )
+        })
+    }
+}
@@ -2,6 +2,8 @@ use std::{ffi::CStr, mem::MaybeUninit};
 
 use crate::{try_uhd, Result, UhdError, Usrp};
 
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HardwareInfo {
     mboard_id: String,
     mboard_name: String,
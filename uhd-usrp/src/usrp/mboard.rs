@@ -1,8 +1,12 @@
-use std::{ffi::CString, ptr::addr_of_mut};
+use std::{
+    ffi::CString,
+    ptr::addr_of_mut,
+    time::{Duration, Instant},
+};
 
 use crate::{
     ffi::{FfiString, FfiStringVec, OwnedHandle},
-    try_uhd, Result, SensorValue, TimeSpec, Usrp,
+    try_uhd, Result, SensorValue, TimeSpec, UhdError, Usrp,
 };
 
 use super::subdev_spec::SubdevSpec;
@@ -94,6 +98,55 @@ impl<'a> Motherboard<'a> {
         Ok(MotherboardEeprom::new(handle))
     }
 
+    /// Writes a modified [`MotherboardEeprom`] back to the device.
+    ///
+    /// Refuses to write if any of [`MotherboardEeprom::REQUIRED_KEYS`] are
+    /// missing from `eeprom`: UHD overwrites the whole EEPROM map in one
+    /// shot, so a partial map would otherwise leave the board unable to
+    /// self-identify on its next boot.
+    pub fn commit_eeprom(&self, eeprom: &MotherboardEeprom) -> Result<()> {
+        for key in MotherboardEeprom::REQUIRED_KEYS {
+            // `value` reads back an empty string for a key the map lacks.
+            if eeprom.value(key).map_or(true, |v| v.is_empty()) {
+                return Err(UhdError::Key);
+            }
+        }
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_usrp_set_mboard_eeprom(
+                self.usrp.handle().as_mut_ptr(),
+                eeprom.handle.as_mut_ptr(),
+                self.mboard,
+            )
+        })
+    }
+
+    /// Writes a modified [`DaughterboardEeprom`] back to the `unit`/`slot`
+    /// daughterboard.
+    ///
+    /// Refuses to write if `eeprom` has no ID set, for the same reason
+    /// [`commit_eeprom`](Self::commit_eeprom) guards the motherboard map.
+    pub fn commit_dboard_eeprom(
+        &self,
+        unit: &str,
+        slot: &str,
+        eeprom: &DaughterboardEeprom,
+    ) -> Result<()> {
+        if eeprom.id()?.is_empty() {
+            return Err(UhdError::Key);
+        }
+        let unit = CString::new(unit).unwrap();
+        let slot = CString::new(slot).unwrap();
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_usrp_set_dboard_eeprom(
+                self.usrp.handle().as_mut_ptr(),
+                eeprom.handle.as_mut_ptr(),
+                unit.as_ptr(),
+                slot.as_ptr(),
+                self.mboard,
+            )
+        })
+    }
+
     /// Get a list of GPIO banks associated with this motherboard.
     pub fn gpio_bank_names(&self) -> Result<Vec<String>> {
         let mut vec = FfiStringVec::new();
@@ -143,6 +196,23 @@ impl<'a> Motherboard<'a> {
         Ok(result)
     }
 
+    /// Set the master clock rate in Hz.
+    ///
+    /// Not all devices support a configurable master clock rate; consult
+    /// the device manual for supported values. Changing this will affect
+    /// derived settings such as sample rate and filter bandwidths, which
+    /// may need to be reconfigured afterwards.
+    pub fn set_master_clock_rate(&mut self, rate: f64) -> Result<()> {
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_usrp_set_master_clock_rate(
+                self.usrp.handle().as_mut_ptr(),
+                self.mboard,
+                rate,
+            )
+        })?;
+        Ok(())
+    }
+
     /// Get canonical name for this USRP motherboard.
     pub fn name(&self) -> Result<String> {
         let mut result = FfiString::with_capacity(32);
@@ -248,13 +318,23 @@ impl<'a> Motherboard<'a> {
         Ok(())
     }
 
-    /// Set the Rx frontend specification.
+    /// Set the Rx frontend specification from a string, such as `"A:A"`.
     pub fn set_rx_subdev_str(&mut self, subdev: &str) -> Result<()> {
-        let sudev = SubdevSpec::from_str(subdev);
+        self.set_rx_subdev(&SubdevSpec::from_str(subdev))
+    }
+
+    /// Set the Rx frontend specification.
+    ///
+    /// This declares which daughterboard/frontend slot(s) drive the Rx
+    /// channels on this motherboard, e.g. a spec parsed from `"A:A A:B"`
+    /// maps channel 0 to the left frontend and channel 1 to the right
+    /// frontend on a B210-style device. Set this before configuring
+    /// per-channel antenna/gain/frequency via [`set_rx_config`](Usrp::set_rx_config).
+    pub fn set_rx_subdev(&mut self, spec: &SubdevSpec) -> Result<()> {
         try_uhd!(unsafe {
             uhd_usrp_sys::uhd_usrp_set_rx_subdev_spec(
                 self.usrp.handle().as_mut_ptr(),
-                sudev.handle().as_mut_ptr(),
+                spec.handle().as_mut_ptr(),
                 self.mboard,
             )
         })?;
@@ -304,6 +384,37 @@ impl<'a> Motherboard<'a> {
         Ok(())
     }
 
+    /// Set the time registers on the USRP at the next PPS rising edge,
+    /// without the caller needing to already know where in the PPS cycle
+    /// the call is being made.
+    ///
+    /// [`set_time_next_pps`](Self::set_time_next_pps) can desynchronize
+    /// timekeepers by exactly one second if it happens to be called close
+    /// to an edge. This avoids that by first polling
+    /// [`last_pps_time`](Self::last_pps_time) until its whole-seconds
+    /// field changes, which marks a PPS edge having just arrived and
+    /// guarantees nearly a full second of slack before the next one, and
+    /// only then issuing `set_time_next_pps(time)`. This takes up to
+    /// roughly a second longer to execute than calling
+    /// `set_time_next_pps` directly.
+    ///
+    /// Returns an error if no edge is observed within about 1.1 seconds
+    /// of polling, which almost certainly means no PPS signal is wired up.
+    pub fn set_time_unknown_pps(&mut self, time: TimeSpec) -> Result<()> {
+        let start_secs = self.last_pps_time()?.full_secs();
+        let deadline = Instant::now() + Duration::from_millis(1100);
+        loop {
+            if self.last_pps_time()?.full_secs() != start_secs {
+                break;
+            }
+            if Instant::now() >= deadline {
+                return Err(UhdError::Runtime);
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        self.set_time_next_pps(time)
+    }
+
     /// Set the time source for the USRP device
     ///
     /// This sets the method of time synchronization, typically a pulse per second signal.
@@ -347,13 +458,19 @@ impl<'a> Motherboard<'a> {
         Ok(())
     }
 
-    /// Set the Tx frontend specification.
+    /// Set the Tx frontend specification from a string, such as `"A:A"`.
     pub fn set_tx_subdev_str(&mut self, subdev: &str) -> Result<()> {
-        let sudev = SubdevSpec::from_str(subdev);
+        self.set_tx_subdev(&SubdevSpec::from_str(subdev))
+    }
+
+    /// Set the Tx frontend specification.
+    ///
+    /// See [`set_rx_subdev`](Self::set_rx_subdev) for the Rx-side equivalent.
+    pub fn set_tx_subdev(&mut self, spec: &SubdevSpec) -> Result<()> {
         try_uhd!(unsafe {
             uhd_usrp_sys::uhd_usrp_set_tx_subdev_spec(
                 self.usrp.handle().as_mut_ptr(),
-                sudev.handle().as_mut_ptr(),
+                spec.handle().as_mut_ptr(),
                 self.mboard,
             )
         })?;
@@ -482,6 +599,168 @@ impl<'a> GpioBank<'a> {
         })?;
         Ok(())
     }
+
+    /// Reads one of UHD's standard GPIO registers, typed instead of by name.
+    pub fn register(&self, reg: GpioRegister) -> Result<u32> {
+        self.attr(reg.as_str())
+    }
+
+    /// Sets the masked bits of one of UHD's standard GPIO registers,
+    /// typed instead of by name. Bits outside `mask` are left untouched.
+    pub fn set_register(&self, reg: GpioRegister, mask: u32, value: u32) -> Result<()> {
+        self.set_attr(reg.as_str(), mask, value)
+    }
+
+    /// Sets a single line's direction without disturbing the rest of the bank.
+    pub fn set_direction(&self, line: u32, direction: Direction) -> Result<()> {
+        let bit = 1 << line;
+        let value = match direction {
+            Direction::Output => bit,
+            Direction::Input => 0,
+        };
+        self.set_register(GpioRegister::Ddr, bit, value)
+    }
+
+    /// Drives a single output line high or low without disturbing the rest of the bank.
+    pub fn write_line(&self, line: u32, high: bool) -> Result<()> {
+        let bit = 1 << line;
+        self.set_register(GpioRegister::Out, bit, if high { bit } else { 0 })
+    }
+
+    /// Reads a single line's current level from the readback register.
+    pub fn read_line(&self, line: u32) -> Result<bool> {
+        Ok(self.register(GpioRegister::Readback)? & (1 << line) != 0)
+    }
+}
+
+/// UHD's standard GPIO bank registers, addressed by name under the hood
+/// (`"DDR"`, `"OUT"`, etc.) but typed here to avoid typos.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GpioRegister {
+    /// Data direction register: `1` bit = output, `0` bit = input.
+    Ddr,
+    /// Manual output value, used for lines not under ATR control.
+    Out,
+    /// Selects, per line, whether [`Out`](Self::Out) or the ATR registers drive it.
+    Ctrl,
+    /// Current level of every line in the bank, regardless of direction.
+    Readback,
+    /// Automatic level driven while neither transmitting nor receiving.
+    Atr0x,
+    /// Automatic level driven while receiving only.
+    AtrRx,
+    /// Automatic level driven while transmitting only.
+    AtrTx,
+    /// Automatic level driven while transmitting and receiving simultaneously.
+    AtrXx,
+}
+
+impl GpioRegister {
+    fn as_str(self) -> &'static str {
+        match self {
+            GpioRegister::Ddr => "DDR",
+            GpioRegister::Out => "OUT",
+            GpioRegister::Ctrl => "CTRL",
+            GpioRegister::Readback => "READBACK",
+            GpioRegister::Atr0x => "ATR_0X",
+            GpioRegister::AtrRx => "ATR_RX",
+            GpioRegister::AtrTx => "ATR_TX",
+            GpioRegister::AtrXx => "ATR_XX",
+        }
+    }
+}
+
+/// A GPIO line's direction, for [`GpioBank::set_direction`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Input,
+    Output,
+}
+
+/// The level a GPIO line should automatically take in each of the four
+/// ATR (Auto Transmit/Receive) duplex states.
+#[derive(Clone, Copy, Debug, Default)]
+struct AtrLevels {
+    idle: bool,
+    rx: bool,
+    tx: bool,
+    full_duplex: bool,
+}
+
+/// Builds an ATR configuration across multiple GPIO lines and commits it
+/// in one call, instead of hand-assembling `DDR`/`CTRL`/`ATR_*` masks.
+///
+/// ATR registers let the radio automatically drive external amplifiers or
+/// antenna switches off its own Tx/Rx state, with no host intervention
+/// once configured. Lines not added via [`with_line`](Self::with_line)
+/// are left untouched by [`commit`](Self::commit).
+#[derive(Clone, Debug, Default)]
+pub struct AtrConfig {
+    lines: Vec<(u32, AtrLevels)>,
+}
+
+impl AtrConfig {
+    pub fn new() -> Self {
+        Self { lines: Vec::new() }
+    }
+
+    /// Declares the level `line` should be driven to in each of the four
+    /// duplex states, overwriting any earlier declaration for that line.
+    pub fn with_line(
+        &mut self,
+        line: u32,
+        idle: bool,
+        rx: bool,
+        tx: bool,
+        full_duplex: bool,
+    ) -> &mut Self {
+        let levels = AtrLevels {
+            idle,
+            rx,
+            tx,
+            full_duplex,
+        };
+        match self.lines.iter_mut().find(|(l, _)| *l == line) {
+            Some((_, existing)) => *existing = levels,
+            None => self.lines.push((line, levels)),
+        }
+        self
+    }
+
+    /// Commits every declared line's ATR configuration to `bank` in one
+    /// pass: puts the lines into output/ATR mode, then programs the four
+    /// per-state registers.
+    pub fn commit(&self, bank: &GpioBank) -> Result<()> {
+        let mut line_mask = 0;
+        let mut atr_0x = 0;
+        let mut atr_rx = 0;
+        let mut atr_tx = 0;
+        let mut atr_xx = 0;
+        for (line, levels) in &self.lines {
+            let bit = 1 << line;
+            line_mask |= bit;
+            if levels.idle {
+                atr_0x |= bit;
+            }
+            if levels.rx {
+                atr_rx |= bit;
+            }
+            if levels.tx {
+                atr_tx |= bit;
+            }
+            if levels.full_duplex {
+                atr_xx |= bit;
+            }
+        }
+
+        bank.set_register(GpioRegister::Ddr, line_mask, line_mask)?;
+        bank.set_register(GpioRegister::Ctrl, line_mask, line_mask)?;
+        bank.set_register(GpioRegister::Atr0x, line_mask, atr_0x)?;
+        bank.set_register(GpioRegister::AtrRx, line_mask, atr_rx)?;
+        bank.set_register(GpioRegister::AtrTx, line_mask, atr_tx)?;
+        bank.set_register(GpioRegister::AtrXx, line_mask, atr_xx)?;
+        Ok(())
+    }
 }
 
 pub struct MotherboardEeprom {
@@ -489,10 +768,41 @@ pub struct MotherboardEeprom {
 }
 
 impl MotherboardEeprom {
+    /// Keys [`Motherboard::commit_eeprom`] requires to be present before
+    /// writing the map back to the device.
+    const REQUIRED_KEYS: &'static [&'static str] = &["name", "serial"];
+
     pub(crate) fn new(handle: OwnedHandle<uhd_usrp_sys::uhd_mboard_eeprom_t>) -> Self {
         Self { handle }
     }
 
+    /// The board's unique serial number.
+    pub fn serial(&self) -> Option<String> {
+        self.value("serial")
+    }
+
+    pub fn set_serial(&self, serial: &str) {
+        self.set_value("serial", serial);
+    }
+
+    /// The board's product name (e.g. `"B210"`).
+    pub fn name(&self) -> Option<String> {
+        self.value("name")
+    }
+
+    pub fn set_name(&self, name: &str) {
+        self.set_value("name", name);
+    }
+
+    /// The board's hardware revision, if the EEPROM map has one.
+    pub fn revision(&self) -> Option<String> {
+        self.value("revision")
+    }
+
+    pub fn set_revision(&self, revision: &str) {
+        self.set_value("revision", revision);
+    }
+
     pub fn value(&self, key: &str) -> Option<String> {
         let key = CString::new(key).unwrap();
         let mut value = FfiString::with_capacity(32);
@@ -594,3 +904,159 @@ impl DaughterboardEeprom {
         unsafe { uhd_usrp_sys::uhd_dboard_eeprom_set_revision(self.handle.as_mut_ptr(), value) };
     }
 }
+
+/// Accumulates clock/time domain settings for a [`Motherboard`] and applies
+/// them in a fixed order via [`apply`](Self::apply). The settings are
+/// issued one call at a time: if one fails, `apply` returns its error and
+/// the settings issued before it stay applied.
+///
+/// On success, [`apply`](Self::apply) hands back a [`LockedMotherboard`],
+/// so code written against one can assume the settings were issued and the
+/// lock check passed. `apply` borrows the builder, which keeps its
+/// settings. See [`LockedMotherboard`] for which setters remain reachable
+/// through it.
+pub struct MotherboardConfig<'a> {
+    mboard: Motherboard<'a>,
+    clock_source: Option<String>,
+    time_source: Option<String>,
+    clock_source_out: Option<bool>,
+    time_source_out: Option<bool>,
+    rx_subdev: Option<String>,
+    tx_subdev: Option<String>,
+    lock_sensor: Option<String>,
+    lock_timeout: Duration,
+}
+
+impl<'a> MotherboardConfig<'a> {
+    /// Start a new configuration for `mboard`. By default, [`apply`](Self::apply)
+    /// waits up to two seconds for a `ref_locked` sensor if one exists; use
+    /// [`lock_sensor`](Self::lock_sensor)/[`lock_timeout`](Self::lock_timeout)
+    /// to change that.
+    pub fn new(mboard: Motherboard<'a>) -> Self {
+        Self {
+            mboard,
+            clock_source: None,
+            time_source: None,
+            clock_source_out: None,
+            time_source_out: None,
+            rx_subdev: None,
+            tx_subdev: None,
+            lock_sensor: Some("ref_locked".to_owned()),
+            lock_timeout: Duration::from_secs(2),
+        }
+    }
+
+    pub fn clock_source(&mut self, source: impl Into<String>) -> &mut Self {
+        self.clock_source = Some(source.into());
+        self
+    }
+
+    pub fn time_source(&mut self, source: impl Into<String>) -> &mut Self {
+        self.time_source = Some(source.into());
+        self
+    }
+
+    pub fn clock_source_out(&mut self, enabled: bool) -> &mut Self {
+        self.clock_source_out = Some(enabled);
+        self
+    }
+
+    pub fn time_source_out(&mut self, enabled: bool) -> &mut Self {
+        self.time_source_out = Some(enabled);
+        self
+    }
+
+    pub fn rx_subdev(&mut self, spec: impl Into<String>) -> &mut Self {
+        self.rx_subdev = Some(spec.into());
+        self
+    }
+
+    pub fn tx_subdev(&mut self, spec: impl Into<String>) -> &mut Self {
+        self.tx_subdev = Some(spec.into());
+        self
+    }
+
+    /// Overrides the sensor [`apply`](Self::apply) polls for lock, or
+    /// disables the lock check entirely if `sensor` is `None`.
+    pub fn lock_sensor(&mut self, sensor: Option<&str>) -> &mut Self {
+        self.lock_sensor = sensor.map(str::to_owned);
+        self
+    }
+
+    /// Overrides how long [`apply`](Self::apply) waits for the lock
+    /// sensor before giving up.
+    pub fn lock_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// Issues every accumulated setting, in the order clock source, time
+    /// source, clock/time reference outputs, then Rx/Tx subdev specs.
+    ///
+    /// If a lock sensor is configured (by default, `ref_locked`, if the
+    /// device reports one) this then polls it until it reads locked,
+    /// returning [`UhdError::Runtime`] if the timeout elapses first.
+    /// Otherwise, hands back a [`LockedMotherboard`] whose clock/time
+    /// domain is guaranteed settled.
+    pub fn apply(&mut self) -> Result<LockedMotherboard<'a>> {
+        if let Some(source) = &self.clock_source {
+            self.mboard.set_clock_source(source)?;
+        }
+        if let Some(source) = &self.time_source {
+            self.mboard.set_time_source(source)?;
+        }
+        if let Some(enabled) = self.clock_source_out {
+            self.mboard.set_clock_source_out(enabled)?;
+        }
+        if let Some(enabled) = self.time_source_out {
+            self.mboard.set_time_source_out(enabled)?;
+        }
+        if let Some(spec) = &self.rx_subdev {
+            self.mboard.set_rx_subdev_str(spec)?;
+        }
+        if let Some(spec) = &self.tx_subdev {
+            self.mboard.set_tx_subdev_str(spec)?;
+        }
+
+        if let Some(sensor) = &self.lock_sensor {
+            if self.mboard.sensor_names()?.iter().any(|n| n == sensor) {
+                let deadline = Instant::now() + self.lock_timeout;
+                loop {
+                    if self.mboard.sensor_value(sensor)?.as_bool() == Some(true) {
+                        break;
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(UhdError::Runtime);
+                    }
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+            }
+        }
+
+        Ok(LockedMotherboard {
+            mboard: Motherboard::new(self.mboard.usrp, self.mboard.mboard),
+        })
+    }
+}
+
+/// A [`Motherboard`] whose clock/time domain has been configured and
+/// (if available) verified locked by [`MotherboardConfig::apply`].
+///
+/// Dereferences to [`Motherboard`], with no `DerefMut`, so the `&mut self`
+/// setters such as [`set_clock_source_out`](Motherboard::set_clock_source_out)
+/// and [`set_rx_subdev`](Motherboard::set_rx_subdev) are not reachable
+/// through it. The `&self` methods are, and they include
+/// [`set_clock_source`](Motherboard::set_clock_source) and
+/// [`set_time_source`](Motherboard::set_time_source), so the clock/time
+/// source can still be changed through this handle.
+pub struct LockedMotherboard<'a> {
+    mboard: Motherboard<'a>,
+}
+
+impl<'a> std::ops::Deref for LockedMotherboard<'a> {
+    type Target = Motherboard<'a>;
+
+    fn deref(&self) -> &Motherboard<'a> {
+        &self.mboard
+    }
+}
@@ -227,3 +227,85 @@ impl RxMetadata {
         TimeSpec::try_from_parts(full_secs, frac_secs)
     }
 }
+
+/// The event reported by a [`TxAsyncMetadata`] message, received via
+/// `uhd_tx_streamer_recv_async_msg`.
+#[derive(Clone, Copy, Debug, num_enum::TryFromPrimitive)]
+#[repr(u32)]
+pub enum AsyncErrorCode {
+    BurstAck = uhd_usrp_sys::uhd_async_metadata_event_code_t::UHD_ASYNC_METADATA_EVENT_CODE_BURST_ACK,
+    Underflow = uhd_usrp_sys::uhd_async_metadata_event_code_t::UHD_ASYNC_METADATA_EVENT_CODE_UNDERFLOW,
+    SequenceError =
+        uhd_usrp_sys::uhd_async_metadata_event_code_t::UHD_ASYNC_METADATA_EVENT_CODE_SEQ_ERROR,
+    TimeError =
+        uhd_usrp_sys::uhd_async_metadata_event_code_t::UHD_ASYNC_METADATA_EVENT_CODE_TIME_ERROR,
+    UnderflowInPacket = uhd_usrp_sys::uhd_async_metadata_event_code_t::UHD_ASYNC_METADATA_EVENT_CODE_UNDERFLOW_IN_PACKET,
+    SequenceErrorInBurst = uhd_usrp_sys::uhd_async_metadata_event_code_t::UHD_ASYNC_METADATA_EVENT_CODE_SEQ_ERROR_IN_BURST,
+    UserPayload =
+        uhd_usrp_sys::uhd_async_metadata_event_code_t::UHD_ASYNC_METADATA_EVENT_CODE_USER_PAYLOAD,
+}
+
+/// Out-of-band status for a previously sent TX packet (e.g. a burst
+/// acknowledgement or an underflow), read back via
+/// [`TxStream::recv_async_msg`](crate::usrp::stream::TxStream::recv_async_msg).
+pub struct TxAsyncMetadata {
+    handle: OwnedHandle<uhd_usrp_sys::uhd_async_metadata_t>,
+}
+
+impl TxAsyncMetadata {
+    pub fn new() -> Self {
+        Self {
+            handle: OwnedHandle::new(
+                uhd_usrp_sys::uhd_async_metadata_make,
+                uhd_usrp_sys::uhd_async_metadata_free,
+            )
+            .expect("uhd_async_metadata_make failed"),
+        }
+    }
+
+    pub(crate) fn handle(&self) -> &OwnedHandle<uhd_usrp_sys::uhd_async_metadata_t> {
+        &self.handle
+    }
+
+    pub fn event_code(&self) -> Result<AsyncErrorCode> {
+        let mut result =
+            uhd_usrp_sys::uhd_async_metadata_event_code_t::UHD_ASYNC_METADATA_EVENT_CODE_BURST_ACK;
+        unsafe {
+            uhd_usrp_sys::uhd_async_metadata_event_code(
+                self.handle.as_mut_ptr(),
+                addr_of_mut!(result),
+            )
+        };
+        Ok(AsyncErrorCode::try_from_primitive(result).or(Err(UhdError::Unknown))?)
+    }
+
+    pub fn time_spec(&self) -> Option<TimeSpec> {
+        let mut has_time_spec = false;
+        unsafe {
+            uhd_usrp_sys::uhd_async_metadata_has_time_spec(
+                self.handle.as_mut_ptr(),
+                addr_of_mut!(has_time_spec),
+            );
+        }
+        if !has_time_spec {
+            return None;
+        }
+
+        let mut full_secs = 0;
+        let mut frac_secs = 0.0;
+        unsafe {
+            uhd_usrp_sys::uhd_async_metadata_time_spec(
+                self.handle.as_mut_ptr(),
+                addr_of_mut!(full_secs),
+                addr_of_mut!(frac_secs),
+            );
+        }
+        TimeSpec::try_from_parts(full_secs, frac_secs)
+    }
+}
+
+impl Default for TxAsyncMetadata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,13 +1,31 @@
 mod channels;
 mod device;
+mod fpga;
 mod hw_info;
 mod mboard;
+mod metadata;
+mod radio;
 pub mod stream;
 mod subdev_spec;
 
-pub use channels::Channel;
+pub use channels::{
+    Channel, ChannelConfiguration, ChannelConfigurationBuilder, ChannelGroupBuilder,
+    ChannelGroupError, ChannelSettings, ChannelSnapshot, LockWaitError, SensorCondition,
+    SensorEvent, SensorMonitor, SensorReading,
+};
 pub use device::Usrp;
+pub use fpga::FpgaImageLoader;
 pub use hw_info::HardwareInfo;
-pub use mboard::{GpioBank, Motherboard};
-pub use stream::{RxStream, TxStream};
+pub use mboard::{
+    AtrConfig, Direction, GpioBank, GpioRegister, LockedMotherboard, Motherboard, MotherboardConfig,
+};
+pub use metadata::{
+    AsyncErrorCode, RxErrorCode, RxMetadata, TxAsyncMetadata, TxMetadata, TxMetadataBuilder,
+};
+pub use radio::RadioDevice;
+pub use stream::{
+    CpuFormat, CpuFormatParseError, DynRxStream, DynRxStreamBuilder, DynSampleBuffer, DynTxStream,
+    DynTxStreamBuilder, Flow, OtwFormat, OtwFormatParseError, RxRunConfig, RxStats, RxStatus,
+    RxStream, StreamHandle, TxStream,
+};
 pub use subdev_spec::{SubdevPair, SubdevSpec, SubdevSpecParseError};
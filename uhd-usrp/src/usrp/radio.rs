@@ -0,0 +1,73 @@
+use crate::{Result, Sample};
+
+use super::{
+    channels::{ChannelConfiguration, ChannelConfigurationBuilder, RX_DIR, TX_DIR},
+    stream::{RxStreamBuilder, TxStreamBuilder},
+    Usrp,
+};
+
+/// Decouples callers from a concrete UHD-backed [`Usrp`] by exposing the
+/// subset of its API needed for channel configuration and streaming.
+///
+/// This mirrors the inherent methods on [`Usrp`] directly; implement it
+/// for a mock type in tests to exercise code that only needs these
+/// operations without a physical device attached.
+pub trait RadioDevice {
+    /// Get the total number of RX channels on this device.
+    fn rx_channels(&self) -> Result<usize>;
+
+    /// Get the total number of TX channels on this device.
+    fn tx_channels(&self) -> Result<usize>;
+
+    /// Read current settings for the given RX channel.
+    fn rx_config(&self, channel: usize) -> ChannelConfiguration<'_, { RX_DIR }>;
+
+    /// Read current settings for the given TX channel.
+    fn tx_config(&self, channel: usize) -> ChannelConfiguration<'_, { TX_DIR }>;
+
+    /// Write settings for the given RX channel.
+    fn set_rx_config(&mut self, channel: usize) -> ChannelConfigurationBuilder<'_, { RX_DIR }>;
+
+    /// Write settings for the given TX channel.
+    fn set_tx_config(&mut self, channel: usize) -> ChannelConfigurationBuilder<'_, { TX_DIR }>;
+
+    /// Returns a builder for opening an RX stream.
+    fn rx_stream<T: Sample>(&self) -> RxStreamBuilder<'_, T>;
+
+    /// Returns a builder for opening a TX stream.
+    fn tx_stream<T: Sample>(&self) -> TxStreamBuilder<'_, T>;
+}
+
+impl RadioDevice for Usrp {
+    fn rx_channels(&self) -> Result<usize> {
+        Usrp::rx_channels(self)
+    }
+
+    fn tx_channels(&self) -> Result<usize> {
+        Usrp::tx_channels(self)
+    }
+
+    fn rx_config(&self, channel: usize) -> ChannelConfiguration<'_, { RX_DIR }> {
+        Usrp::rx_config(self, channel)
+    }
+
+    fn tx_config(&self, channel: usize) -> ChannelConfiguration<'_, { TX_DIR }> {
+        Usrp::tx_config(self, channel)
+    }
+
+    fn set_rx_config(&mut self, channel: usize) -> ChannelConfigurationBuilder<'_, { RX_DIR }> {
+        Usrp::set_rx_config(self, channel)
+    }
+
+    fn set_tx_config(&mut self, channel: usize) -> ChannelConfigurationBuilder<'_, { TX_DIR }> {
+        Usrp::set_tx_config(self, channel)
+    }
+
+    fn rx_stream<T: Sample>(&self) -> RxStreamBuilder<'_, T> {
+        Usrp::rx_stream(self)
+    }
+
+    fn tx_stream<T: Sample>(&self) -> TxStreamBuilder<'_, T> {
+        Usrp::tx_stream(self)
+    }
+}
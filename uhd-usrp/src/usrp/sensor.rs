@@ -15,15 +15,17 @@ impl SensorValue {
     }
 
     pub fn name(&self) -> String {
-        let mut s = FfiString::with_capacity(32);
-        unsafe {
-            uhd_usrp_sys::uhd_sensor_value_name(
-                self.handle.as_mut_ptr(),
-                s.as_mut_ptr(),
-                s.max_chars(),
-            )
-        };
-        s.into_string().unwrap()
+        FfiString::get_with_retry(32, 4096, |s| {
+            unsafe {
+                uhd_usrp_sys::uhd_sensor_value_name(
+                    self.handle.as_mut_ptr(),
+                    s.as_mut_ptr(),
+                    s.max_chars(),
+                )
+            };
+            Ok(())
+        })
+        .unwrap()
     }
 
     pub fn to_bool(&self) -> bool {
@@ -51,15 +53,15 @@ impl SensorValue {
     }
 
     pub fn to_pp_string(&self) -> Result<String> {
-        let mut value = FfiString::with_capacity(64);
-        try_uhd!(unsafe {
-            uhd_usrp_sys::uhd_sensor_value_to_pp_string(
-                self.handle.as_mut_ptr(),
-                value.as_mut_ptr(),
-                value.max_chars(),
-            )
-        })?;
-        value.into_string()
+        FfiString::get_with_retry(64, 4096, |value| {
+            try_uhd!(unsafe {
+                uhd_usrp_sys::uhd_sensor_value_to_pp_string(
+                    self.handle.as_mut_ptr(),
+                    value.as_mut_ptr(),
+                    value.max_chars(),
+                )
+            })
+        })
     }
 
     pub fn to_string(&self) -> String {
@@ -75,14 +77,15 @@ impl SensorValue {
     }
 
     pub fn unit(&self) -> Result<String> {
-        let mut value = FfiString::with_capacity(64);
-        unsafe {
-            uhd_usrp_sys::uhd_sensor_value_unit(
-                self.handle.as_mut_ptr(),
-                value.as_mut_ptr(),
-                value.max_chars(),
-            )
-        };
-        value.into_string()
+        FfiString::get_with_retry(64, 4096, |value| {
+            unsafe {
+                uhd_usrp_sys::uhd_sensor_value_unit(
+                    self.handle.as_mut_ptr(),
+                    value.as_mut_ptr(),
+                    value.max_chars(),
+                )
+            };
+            Ok(())
+        })
     }
 }
@@ -0,0 +1,166 @@
+//! Async wrappers around [`TxStream::send`](super::TxStreamWriter::send) and
+//! [`RxStream::recv`](super::RxStreamReader::recv).
+//!
+//! UHD's C API only exposes blocking `uhd_tx_streamer_send`/
+//! `uhd_rx_streamer_recv` calls with a timeout, so these are driven on a
+//! [`tokio::task::spawn_blocking`] worker rather than the async executor
+//! itself. Large transfers are chunked into
+//! [`TxStream::max_samples_per_channel`]/[`RxStream::max_samples_per_channel`]-sized
+//! pieces so a single `.await` doesn't monopolize a blocking-pool thread
+//! for the whole transfer; each chunk completes by way of the
+//! [`tokio::task::JoinHandle`] spawn_blocking itself hands back over a
+//! oneshot channel.
+
+use std::time::{Duration, Instant};
+
+use super::{RxStream, TxStream};
+use crate::{Result, Sample, UhdError};
+
+/// Wraps a raw pointer so it can be moved into a `spawn_blocking` closure.
+///
+/// # Safety
+///
+/// The caller must ensure the pointee outlives the blocking closure. Since
+/// `send_async`/`recv_async` hold `&mut self` for the lifetime of the
+/// returned future and `.await` it to completion before returning, this
+/// holds as long as the future isn't leaked (e.g. via `mem::forget`)
+/// while the blocking task is still in flight.
+struct SendPtr<T>(*mut T);
+unsafe impl<T> Send for SendPtr<T> {}
+
+impl<T: Sample> TxStream<T> {
+    /// Send `buffers` (one slice per channel) asynchronously.
+    ///
+    /// See the [module docs](self) for how this is driven under the hood.
+    pub fn send_async<'a>(
+        &'a mut self,
+        buffers: &'a [&'a [T]],
+        timeout: Duration,
+    ) -> impl std::future::Future<Output = Result<usize>> + 'a {
+        async move {
+            let chunk_len = self.max_samples_per_channel();
+            let total = buffers.first().map_or(0, |b| b.len());
+            let stream = SendPtr(self as *mut Self);
+            let mut sent = 0;
+            while sent < total {
+                let end = (sent + chunk_len).min(total);
+                let ptrs: Vec<*const T> = buffers.iter().map(|b| b[sent..end].as_ptr()).collect();
+                let chunk_ptrs = SendPtr(ptrs.as_ptr() as *mut *const T);
+                let samples = end - sent;
+                let n = tokio::task::spawn_blocking(move || {
+                    // Safety: `stream` and `chunk_ptrs` are kept alive by
+                    // the enclosing future until this blocking call
+                    // returns (see `SendPtr`'s safety note).
+                    let stream = unsafe { &mut *stream.0 };
+                    unsafe {
+                        stream
+                            .writer()
+                            .with_timeout(timeout)
+                            .send_raw(chunk_ptrs.0.cast(), samples)
+                    }
+                })
+                .await
+                .map_err(|_| UhdError::Runtime)??;
+                drop(ptrs);
+                sent += n;
+                if n == 0 {
+                    break;
+                }
+            }
+            Ok(sent)
+        }
+    }
+}
+
+impl<T: Sample> RxStream<T> {
+    /// Receive into `buffers` (one slice per channel) asynchronously.
+    ///
+    /// See the [module docs](self) for how this is driven under the hood.
+    pub fn recv_async<'a>(
+        &'a mut self,
+        buffers: &'a mut [&'a mut [T]],
+        timeout: Duration,
+    ) -> impl std::future::Future<Output = Result<usize>> + 'a {
+        async move {
+            let chunk_len = self.max_samples_per_channel();
+            let total = buffers.first().map_or(0, |b| b.len());
+            let stream = SendPtr(self as *mut Self);
+            let mut received = 0;
+            while received < total {
+                let end = (received + chunk_len).min(total);
+                let mut ptrs: Vec<*mut T> = buffers
+                    .iter_mut()
+                    .map(|b| b[received..end].as_mut_ptr())
+                    .collect();
+                let chunk_ptrs = SendPtr(ptrs.as_mut_ptr());
+                let samples = end - received;
+                let n = tokio::task::spawn_blocking(move || {
+                    // Safety: see `SendPtr`'s safety note above.
+                    let stream = unsafe { &mut *stream.0 };
+                    unsafe {
+                        stream
+                            .reader()
+                            .with_timeout(timeout)
+                            .recv_raw(chunk_ptrs.0, samples)
+                    }
+                })
+                .await
+                .map_err(|_| UhdError::Runtime)??;
+                drop(ptrs);
+                received += n;
+                if n == 0 {
+                    break;
+                }
+            }
+            Ok(received)
+        }
+    }
+
+    /// Wait for a single packet to arrive, without blocking the async
+    /// executor.
+    ///
+    /// Unlike [`recv_async`](Self::recv_async), which chunks a known-size
+    /// transfer, this is for integrating UHD receive into a tokio/async-std
+    /// `select!` loop: internally it repeatedly drives `uhd_rx_streamer_recv`
+    /// with `one_packet = true` and a short retry timeout on a single
+    /// `spawn_blocking` worker, looping until a packet arrives or
+    /// `deadline` elapses. That blocking-pool thread stays occupied for the
+    /// whole wait.
+    pub fn recv_packet_async<'a>(
+        &'a mut self,
+        buffers: &'a mut [&'a mut [T]],
+        deadline: Duration,
+    ) -> impl std::future::Future<Output = Result<usize>> + 'a {
+        const RETRY_TIMEOUT: Duration = Duration::from_millis(50);
+
+        async move {
+            let samples = buffers.first().map_or(0, |b| b.len());
+            let mut ptrs: Vec<*mut T> = buffers.iter_mut().map(|b| b.as_mut_ptr()).collect();
+            let stream = SendPtr(self as *mut Self);
+            let chunk_ptrs = SendPtr(ptrs.as_mut_ptr());
+
+            let result = tokio::task::spawn_blocking(move || {
+                // Safety: see `SendPtr`'s safety note above.
+                let stream = unsafe { &mut *stream.0 };
+                let start = Instant::now();
+                loop {
+                    let remaining = deadline.saturating_sub(start.elapsed());
+                    let n = unsafe {
+                        stream
+                            .reader()
+                            .with_timeout(RETRY_TIMEOUT.min(remaining))
+                            .with_one_packet(true)
+                            .recv_raw(chunk_ptrs.0, samples)
+                    }?;
+                    if n > 0 || start.elapsed() >= deadline {
+                        return Ok(n);
+                    }
+                }
+            })
+            .await
+            .map_err(|_| UhdError::Runtime)?;
+            drop(ptrs);
+            result
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::{
+    sync::{atomic::AtomicBool, Arc},
+    thread::JoinHandle,
+};
+
+/// A guard for a worker thread started by [`TxStream::run_output`](super::TxStream::run_output)
+/// or [`RxStream::run_input`](super::RxStream::run_input).
+///
+/// Dropping it (or calling [`stop`](Self::stop) explicitly) signals the
+/// worker to wind down — ending its current burst and joining — rather
+/// than leaving it running detached.
+pub struct StreamHandle {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl StreamHandle {
+    pub(crate) fn new(stop: Arc<AtomicBool>, handle: JoinHandle<()>) -> Self {
+        Self {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Signal the worker thread to stop and block until it has.
+    pub fn stop(mut self) {
+        self.stop_and_join();
+    }
+
+    fn stop_and_join(&mut self) {
+        self.stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for StreamHandle {
+    fn drop(&mut self) {
+        self.stop_and_join();
+    }
+}
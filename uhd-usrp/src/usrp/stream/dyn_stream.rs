@@ -0,0 +1,436 @@
+use std::{collections::HashMap, ffi::CString, ptr::addr_of_mut, time::Duration};
+
+use super::{CpuFormat, OtwFormat};
+use crate::{
+    error::try_uhd,
+    ffi::OwnedHandle,
+    types::DeviceArgs,
+    usrp::{metadata::TxMetadata, Usrp},
+    Result, UhdError,
+};
+
+/// A byte buffer tagged with the [`CpuFormat`] it was built for.
+///
+/// [`DynTxStream::send_buffer`]/[`DynRxStream::recv_buffer`] check this
+/// against the stream's own [`cpu_format`](DynTxStream::cpu_format)
+/// before dispatching, so a buffer built for the wrong format is rejected
+/// instead of silently being reinterpreted as raw bytes.
+pub struct DynSampleBuffer<B> {
+    format: CpuFormat,
+    channels: B,
+}
+
+impl<B> DynSampleBuffer<B> {
+    pub fn new(format: CpuFormat, channels: B) -> Self {
+        Self { format, channels }
+    }
+
+    pub fn sample_format(&self) -> CpuFormat {
+        self.format
+    }
+}
+
+/// Builder for a [`DynTxStream`], whose CPU format is chosen at runtime via
+/// a [`CpuFormat`] instead of a [`Sample`](crate::Sample) type parameter.
+pub struct DynTxStreamBuilder<'usrp> {
+    usrp: &'usrp Usrp,
+    cpu_format: CpuFormat,
+    otw_format: Option<OtwFormat>,
+    args: HashMap<String, String>,
+    channels: Vec<usize>,
+}
+
+impl<'usrp> DynTxStreamBuilder<'usrp> {
+    pub(crate) fn new(usrp: &'usrp Usrp, cpu_format: CpuFormat) -> Self {
+        Self {
+            usrp,
+            cpu_format,
+            otw_format: None,
+            args: HashMap::new(),
+            channels: vec![0],
+        }
+    }
+
+    /// Specify the "over the wire" format to use.
+    ///
+    /// If unspecified, a format will be chosen automatically.
+    pub fn with_otw_format(&mut self, format: OtwFormat) -> &mut Self {
+        self.otw_format = Some(format);
+        self
+    }
+
+    /// Specify which channels will be used for transmission.
+    ///
+    /// Defaults to a single channel, `0`.
+    pub fn with_channels(&mut self, channels: &[usize]) -> &mut Self {
+        self.channels = channels.to_vec();
+        self
+    }
+
+    /// Specify a keyword argument for the stream.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if either `arg` or `value` contains an `'='` character
+    /// or null byte.
+    pub fn with_kwarg(&mut self, arg: &str, value: &str) -> &mut Self {
+        assert!(!arg.contains('='), "argument cannot contain '='");
+        assert!(!value.contains('='), "value cannot contain '='");
+        assert!(!arg.contains('\0'), "argument cannot contain null bytes");
+        assert!(!value.contains('\0'), "value cannot contain null bytes");
+
+        self.args.insert(arg.to_string(), value.to_string());
+        self
+    }
+
+    /// Merge a [`DeviceArgs`] builder's keys into this stream's arguments.
+    pub fn with_device_args(&mut self, args: DeviceArgs) -> &mut Self {
+        for kv in args.iter() {
+            if let Some((key, value)) = kv.split_once('=') {
+                self.args.insert(key.to_string(), value.to_string());
+            }
+        }
+        self
+    }
+
+    /// Open the TX stream using the previously-specified arguments.
+    #[must_use]
+    pub fn open(&self) -> Result<DynTxStream> {
+        let mut handle: uhd_usrp_sys::uhd_tx_streamer_handle = std::ptr::null_mut();
+        if let Err(e) = try_uhd!(unsafe { uhd_usrp_sys::uhd_tx_streamer_make(&mut handle) }) {
+            unsafe { uhd_usrp_sys::uhd_tx_streamer_free(addr_of_mut!(handle)) };
+            return Err(e);
+        }
+
+        let cpu_format = CString::new(self.cpu_format.as_str()).unwrap();
+        let otw_format = self.otw_format.map(|f| f.as_str()).unwrap_or("");
+        let args = CString::new(
+            self.args
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+        .unwrap();
+        let mut stream_args = uhd_usrp_sys::uhd_stream_args_t {
+            cpu_format: cpu_format.as_ptr() as *mut _,
+            otw_format: otw_format.as_ptr() as *mut _,
+            args: args.as_ptr() as *mut _,
+            channel_list: self.channels.as_ptr().cast_mut(),
+            n_channels: self.channels.len() as i32,
+        };
+
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_usrp_get_tx_stream(
+                self.usrp.handle().as_mut_ptr(),
+                addr_of_mut!(stream_args),
+                handle,
+            )
+        })?;
+        DynTxStream::new(
+            unsafe { OwnedHandle::from_ptr(handle, uhd_usrp_sys::uhd_tx_streamer_free) },
+            self.cpu_format,
+        )
+    }
+}
+
+/// A type-erased TX stream whose element size and CPU format are carried at
+/// runtime instead of through a [`Sample`](crate::Sample) type parameter.
+///
+/// Accepts raw `&[u8]` buffers, validating their length against
+/// [`cpu_format`](Self::cpu_format)'s [`element_size`](CpuFormat::element_size).
+/// Useful for generic recorders/players that choose their wire and host
+/// formats from a config file at runtime.
+pub struct DynTxStream {
+    handle: OwnedHandle<uhd_usrp_sys::uhd_tx_streamer>,
+    samples_per_buffer: usize,
+    channels: usize,
+    cpu_format: CpuFormat,
+}
+
+impl DynTxStream {
+    fn new(handle: OwnedHandle<uhd_usrp_sys::uhd_tx_streamer>, cpu_format: CpuFormat) -> Result<Self> {
+        let mut spb = 0;
+        let mut channels = 0;
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_tx_streamer_max_num_samps(handle.as_mut_ptr(), addr_of_mut!(spb))
+        })?;
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_tx_streamer_num_channels(handle.as_mut_ptr(), addr_of_mut!(channels))
+        })?;
+
+        Ok(Self {
+            handle,
+            samples_per_buffer: spb,
+            channels,
+            cpu_format,
+        })
+    }
+
+    pub fn cpu_format(&self) -> CpuFormat {
+        self.cpu_format
+    }
+
+    pub fn max_samples_per_channel(&self) -> usize {
+        self.samples_per_buffer
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Send one byte slice per channel, blocking for up to `timeout`.
+    ///
+    /// Returns the number of samples (not bytes) sent per channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any buffer's length isn't a multiple of
+    /// [`cpu_format`](Self::cpu_format)'s element size.
+    pub fn send(&mut self, buffers: &[&[u8]], timeout: Duration) -> Result<usize> {
+        let element_size = self.cpu_format.element_size();
+        for buf in buffers {
+            assert_eq!(
+                buf.len() % element_size,
+                0,
+                "buffer length must be a multiple of the CPU format's element size"
+            );
+        }
+        let samples_per_channel = buffers.first().map_or(0, |b| b.len() / element_size);
+        let ptrs: Vec<*const u8> = buffers.iter().map(|b| b.as_ptr()).collect();
+
+        let mut sent = 0;
+        let metadata_handle = TxMetadata::new().to_handle();
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_tx_streamer_send(
+                self.handle.as_mut_ptr(),
+                ptrs.as_ptr().cast_mut().cast(),
+                samples_per_channel,
+                metadata_handle.as_mut_mut_ptr(),
+                timeout.as_secs_f64(),
+                addr_of_mut!(sent),
+            )
+        })?;
+        Ok(sent)
+    }
+
+    /// Like [`send`](Self::send), but checks `buf`'s own
+    /// [`sample_format`](DynSampleBuffer::sample_format) against this
+    /// stream's [`cpu_format`](Self::cpu_format) first, returning
+    /// [`UhdError::Type`] on a mismatch instead of dispatching bytes built
+    /// for a different format.
+    pub fn send_buffer(
+        &mut self,
+        buf: &DynSampleBuffer<Vec<&[u8]>>,
+        timeout: Duration,
+    ) -> Result<usize> {
+        if buf.sample_format() != self.cpu_format {
+            return Err(UhdError::Type);
+        }
+        self.send(&buf.channels, timeout)
+    }
+}
+
+unsafe impl Send for DynTxStream {}
+
+/// Builder for a [`DynRxStream`], whose CPU format is chosen at runtime via
+/// a [`CpuFormat`] instead of a [`Sample`](crate::Sample) type parameter.
+pub struct DynRxStreamBuilder<'usrp> {
+    usrp: &'usrp Usrp,
+    cpu_format: CpuFormat,
+    otw_format: Option<OtwFormat>,
+    args: HashMap<String, String>,
+    channels: Vec<usize>,
+}
+
+impl<'usrp> DynRxStreamBuilder<'usrp> {
+    pub(crate) fn new(usrp: &'usrp Usrp, cpu_format: CpuFormat) -> Self {
+        Self {
+            usrp,
+            cpu_format,
+            otw_format: None,
+            args: HashMap::new(),
+            channels: vec![0],
+        }
+    }
+
+    /// Specify the "over the wire" format to use.
+    ///
+    /// If unspecified, a format will be chosen automatically.
+    pub fn with_otw_format(&mut self, format: OtwFormat) -> &mut Self {
+        self.otw_format = Some(format);
+        self
+    }
+
+    /// Specify which channels will be used for reception.
+    ///
+    /// Defaults to a single channel, `0`.
+    pub fn with_channels(&mut self, channels: &[usize]) -> &mut Self {
+        self.channels = channels.to_vec();
+        self
+    }
+
+    /// Specify a keyword argument for the stream.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if either `arg` or `value` contains an `'='` character
+    /// or null byte.
+    pub fn with_kwarg(&mut self, arg: &str, value: &str) -> &mut Self {
+        assert!(!arg.contains('='), "argument cannot contain '='");
+        assert!(!value.contains('='), "value cannot contain '='");
+        assert!(!arg.contains('\0'), "argument cannot contain null bytes");
+        assert!(!value.contains('\0'), "value cannot contain null bytes");
+
+        self.args.insert(arg.to_string(), value.to_string());
+        self
+    }
+
+    /// Merge a [`DeviceArgs`] builder's keys into this stream's arguments.
+    pub fn with_device_args(&mut self, args: DeviceArgs) -> &mut Self {
+        for kv in args.iter() {
+            if let Some((key, value)) = kv.split_once('=') {
+                self.args.insert(key.to_string(), value.to_string());
+            }
+        }
+        self
+    }
+
+    /// Open the RX stream using the previously-specified arguments.
+    #[must_use]
+    pub fn open(&self) -> Result<DynRxStream> {
+        let mut handle: uhd_usrp_sys::uhd_rx_streamer_handle = std::ptr::null_mut();
+        if let Err(e) = try_uhd!(unsafe { uhd_usrp_sys::uhd_rx_streamer_make(&mut handle) }) {
+            unsafe { uhd_usrp_sys::uhd_rx_streamer_free(addr_of_mut!(handle)) };
+            return Err(e);
+        }
+
+        let cpu_format = CString::new(self.cpu_format.as_str()).unwrap();
+        let otw_format = self.otw_format.map(|f| f.as_str()).unwrap_or("");
+        let args = CString::new(
+            self.args
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+        .unwrap();
+        let mut channels = self.channels.clone();
+        let mut stream_args = uhd_usrp_sys::uhd_stream_args_t {
+            cpu_format: cpu_format.as_ptr() as *mut _,
+            otw_format: otw_format.as_ptr() as *mut _,
+            args: args.as_ptr() as *mut _,
+            channel_list: channels.as_mut_ptr(),
+            n_channels: channels.len() as i32,
+        };
+
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_usrp_get_rx_stream(
+                self.usrp.handle().as_mut_ptr(),
+                addr_of_mut!(stream_args),
+                handle,
+            )
+        })?;
+        DynRxStream::new(
+            unsafe { OwnedHandle::from_ptr(handle, uhd_usrp_sys::uhd_rx_streamer_free) },
+            self.cpu_format,
+        )
+    }
+}
+
+/// A type-erased RX stream whose element size and CPU format are carried at
+/// runtime instead of through a [`Sample`](crate::Sample) type parameter.
+///
+/// Accepts raw `&mut [u8]` buffers, validating their length against
+/// [`cpu_format`](Self::cpu_format)'s [`element_size`](CpuFormat::element_size).
+pub struct DynRxStream {
+    handle: OwnedHandle<uhd_usrp_sys::uhd_rx_streamer>,
+    samples_per_buffer: usize,
+    channels: usize,
+    cpu_format: CpuFormat,
+}
+
+impl DynRxStream {
+    fn new(handle: OwnedHandle<uhd_usrp_sys::uhd_rx_streamer>, cpu_format: CpuFormat) -> Result<Self> {
+        let mut spb = 0;
+        let mut channels = 0;
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rx_streamer_max_num_samps(handle.as_mut_ptr(), addr_of_mut!(spb))
+        })?;
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rx_streamer_num_channels(handle.as_mut_ptr(), addr_of_mut!(channels))
+        })?;
+
+        Ok(Self {
+            handle,
+            samples_per_buffer: spb,
+            channels,
+            cpu_format,
+        })
+    }
+
+    pub fn cpu_format(&self) -> CpuFormat {
+        self.cpu_format
+    }
+
+    pub fn max_samples_per_channel(&self) -> usize {
+        self.samples_per_buffer
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Fill one byte slice per channel, blocking for up to `timeout`.
+    ///
+    /// Returns the number of samples (not bytes) received per channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any buffer's length isn't a multiple of
+    /// [`cpu_format`](Self::cpu_format)'s element size.
+    pub fn recv(&mut self, buffers: &mut [&mut [u8]], timeout: Duration) -> Result<usize> {
+        let element_size = self.cpu_format.element_size();
+        for buf in buffers.iter() {
+            assert_eq!(
+                buf.len() % element_size,
+                0,
+                "buffer length must be a multiple of the CPU format's element size"
+            );
+        }
+        let samples_per_channel = buffers.first().map_or(0, |b| b.len() / element_size);
+        let mut ptrs: Vec<*mut u8> = buffers.iter_mut().map(|b| b.as_mut_ptr()).collect();
+
+        let mut received = 0;
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_rx_streamer_recv(
+                self.handle.as_mut_ptr(),
+                ptrs.as_mut_ptr().cast(),
+                samples_per_channel,
+                std::ptr::null_mut(),
+                timeout.as_secs_f64(),
+                false,
+                addr_of_mut!(received),
+            )
+        })?;
+        Ok(received)
+    }
+
+    /// Like [`recv`](Self::recv), but checks `buf`'s own
+    /// [`sample_format`](DynSampleBuffer::sample_format) against this
+    /// stream's [`cpu_format`](Self::cpu_format) first, returning
+    /// [`UhdError::Type`] on a mismatch instead of dispatching bytes built
+    /// for a different format.
+    pub fn recv_buffer(
+        &mut self,
+        buf: &mut DynSampleBuffer<Vec<&mut [u8]>>,
+        timeout: Duration,
+    ) -> Result<usize> {
+        if buf.sample_format() != self.cpu_format {
+            return Err(UhdError::Type);
+        }
+        self.recv(&mut buf.channels, timeout)
+    }
+}
+
+unsafe impl Send for DynRxStream {}
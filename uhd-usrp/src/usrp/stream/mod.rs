@@ -1,9 +1,106 @@
+use std::str::FromStr;
+
+#[cfg(feature = "async")]
+mod async_stream;
+mod callback;
+mod dyn_stream;
 mod rx_stream;
 mod tx_stream;
 
-pub use rx_stream::{RxStream, RxStreamBuilder, RxStreamReader};
+pub use callback::StreamHandle;
+pub use dyn_stream::{
+    DynRxStream, DynRxStreamBuilder, DynSampleBuffer, DynTxStream, DynTxStreamBuilder,
+};
+pub use rx_stream::{
+    Flow, RxLoopMetrics, RxRunConfig, RxStats, RxStatus, RxStream, RxStreamBuilder, RxStreamReader,
+};
 pub use tx_stream::{TxStream, TxStreamBuilder, TxStreamWriter};
 
+/// The CPU-side (host memory layout) sample format, as data rather than a
+/// [`Sample`](crate::Sample) type parameter.
+///
+/// Used by [`DynTxStream`]/[`DynRxStream`] so the format can be chosen at
+/// runtime (e.g. from a config file) instead of being fixed at compile
+/// time through `T::name()`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CpuFormat {
+    ComplexFloat32,
+    ComplexFloat64,
+    ComplexInt16,
+    ComplexInt8,
+    Int16,
+    Int8,
+}
+
+impl CpuFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CpuFormat::ComplexFloat32 => "fc32",
+            CpuFormat::ComplexFloat64 => "fc64",
+            CpuFormat::ComplexInt16 => "sc16",
+            CpuFormat::ComplexInt8 => "sc8",
+            CpuFormat::Int16 => "s16",
+            CpuFormat::Int8 => "s8",
+        }
+    }
+
+    /// The size, in bytes, of a single sample in this format.
+    pub fn element_size(&self) -> usize {
+        match self {
+            CpuFormat::ComplexFloat32 => 8,
+            CpuFormat::ComplexFloat64 => 16,
+            CpuFormat::ComplexInt16 => 4,
+            CpuFormat::ComplexInt8 => 2,
+            CpuFormat::Int16 => 2,
+            CpuFormat::Int8 => 1,
+        }
+    }
+}
+
+/// The error returned when parsing a [`CpuFormat`] from a string that
+/// isn't one of its known tokens (`"fc32"`, `"fc64"`, `"sc16"`, `"sc8"`,
+/// `"s16"`, `"s8"`).
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+#[error("unknown CPU format: {0}")]
+pub struct CpuFormatParseError(String);
+
+impl FromStr for CpuFormat {
+    type Err = CpuFormatParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fc32" => Ok(CpuFormat::ComplexFloat32),
+            "fc64" => Ok(CpuFormat::ComplexFloat64),
+            "sc16" => Ok(CpuFormat::ComplexInt16),
+            "sc8" => Ok(CpuFormat::ComplexInt8),
+            "s16" => Ok(CpuFormat::Int16),
+            "s8" => Ok(CpuFormat::Int8),
+            _ => Err(CpuFormatParseError(s.to_owned())),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CpuFormat {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CpuFormat {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let token = String::deserialize(deserializer)?;
+        token.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OtwFormat {
     ComplexInt16,
@@ -24,3 +121,45 @@ impl OtwFormat {
         }
     }
 }
+
+/// The error returned when parsing an [`OtwFormat`] from a string that
+/// isn't one of its known tokens (`"sc16"`, `"sc12"`, `"sc8"`, `"s16"`, `"s8"`).
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+#[error("unknown OTW format: {0}")]
+pub struct OtwFormatParseError(String);
+
+impl FromStr for OtwFormat {
+    type Err = OtwFormatParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sc16" => Ok(OtwFormat::ComplexInt16),
+            "sc12" => Ok(OtwFormat::ComplexInt12),
+            "sc8" => Ok(OtwFormat::ComplexInt8),
+            "s16" => Ok(OtwFormat::Int16),
+            "s8" => Ok(OtwFormat::Int8),
+            _ => Err(OtwFormatParseError(s.to_owned())),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for OtwFormat {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OtwFormat {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let token = String::deserialize(deserializer)?;
+        token.parse().map_err(serde::de::Error::custom)
+    }
+}
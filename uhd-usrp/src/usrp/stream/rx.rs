@@ -146,6 +146,7 @@ impl<'a, T: SampleType> RxStreamReaderOptions<'a, T> {
             stream: self.stream,
             timeout: self.timeout,
             one_packet: self.one_packet,
+            ptr_buffs: Vec::with_capacity(self.stream.channels()),
         })
     }
 }
@@ -154,6 +155,11 @@ pub struct RxStreamReader<'a, T: SampleType> {
     stream: &'a RxStream<T>,
     timeout: Option<Duration>,
     one_packet: bool,
+    /// Scratch array-of-pointers handed to `uhd_rx_streamer_recv`, reused
+    /// across calls so the hot receive loop doesn't allocate every time.
+    /// Cleared and re-filled each [`recv`](Self::recv), but the backing
+    /// allocation is kept.
+    ptr_buffs: Vec<*mut T>,
 }
 
 impl<'a, T: SampleType> RxStreamReader<'a, T> {
@@ -161,16 +167,15 @@ impl<'a, T: SampleType> RxStreamReader<'a, T> {
         if buffs.len() > 1 && buffs.iter().any(|e| e.len() != buffs[0].len()) {
             return Err(UhdError::Index);
         }
-        let mut ptr_buffs = buffs
-            .iter()
-            .map(|buff| buff.as_ptr().cast_mut())
-            .collect::<Vec<_>>();
+        self.ptr_buffs.clear();
+        self.ptr_buffs
+            .extend(buffs.iter().map(|buff| buff.as_ptr().cast_mut()));
         let mut received = 0;
         let mut handle = metadata.handle();
         try_uhd!(unsafe {
             uhd_usrp_sys::uhd_rx_streamer_recv(
                 self.stream.handle,
-                ptr_buffs.as_mut_ptr().cast(),
+                self.ptr_buffs.as_mut_ptr().cast(),
                 buffs[0].len(),
                 addr_of_mut!(handle),
                 self.timeout.unwrap_or(Duration::ZERO).as_secs_f64(),
@@ -4,15 +4,24 @@ use std::{
     ffi::CString,
     marker::PhantomData,
     ptr::{addr_of, addr_of_mut},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
     time::Duration,
 };
 
-use super::OtwFormat;
+use super::{callback::StreamHandle, OtwFormat};
 use crate::{
     buffer::SampleBuffer,
     error::try_uhd,
     ffi::OwnedHandle,
-    usrp::{metadata::RxMetadata, Usrp},
+    types::DeviceArgs,
+    usrp::{
+        metadata::{RxErrorCode, RxMetadata},
+        Usrp,
+    },
     Result, Sample, TimeSpec, UhdError,
 };
 
@@ -79,6 +88,21 @@ where
         self
     }
 
+    /// Merge a [`DeviceArgs`] builder's keys into this stream's arguments.
+    ///
+    /// This allows the same typed argument builder used for
+    /// [`Usrp::open`](crate::Usrp::open) and [`TuneRequest`](crate::types::TuneRequest)
+    /// to also be used for stream construction, instead of repeating
+    /// individual [`with_kwarg`](Self::with_kwarg) calls.
+    pub fn with_device_args(&mut self, args: DeviceArgs) -> &mut Self {
+        for kv in args.iter() {
+            if let Some((key, value)) = kv.split_once('=') {
+                self.args.insert(key.to_string(), value.to_string());
+            }
+        }
+        self
+    }
+
     /// Open the RX stream using the previously-specified arguments.
     #[must_use]
     pub fn open(&self) -> Result<RxStream<T>> {
@@ -114,9 +138,10 @@ where
                 handle,
             )
         })?;
-        RxStream::<T>::new(unsafe {
-            OwnedHandle::from_ptr(handle, uhd_usrp_sys::uhd_rx_streamer_free)
-        })
+        RxStream::<T>::new(
+            unsafe { OwnedHandle::from_ptr(handle, uhd_usrp_sys::uhd_rx_streamer_free) },
+            self.otw_format,
+        )
     }
 }
 
@@ -127,6 +152,8 @@ where
     handle: RxStreamHandle,
     samples_per_buffer: usize,
     channels: usize,
+    otw_format: Option<OtwFormat>,
+    stats: RxStats,
 
     _unsync: PhantomData<Cell<T>>,
 }
@@ -137,7 +164,7 @@ impl<T> RxStream<T>
 where
     T: Sample,
 {
-    pub(crate) fn new(handle: RxStreamHandle) -> Result<Self> {
+    pub(crate) fn new(handle: RxStreamHandle, otw_format: Option<OtwFormat>) -> Result<Self> {
         let mut spb = 0;
         let mut channels = 0;
         try_uhd!(unsafe {
@@ -151,6 +178,8 @@ where
             handle,
             samples_per_buffer: spb,
             channels,
+            otw_format,
+            stats: RxStats::default(),
             _unsync: PhantomData::default(),
         })
     }
@@ -163,10 +192,24 @@ where
         self.samples_per_buffer
     }
 
+    /// Receive-health statistics accumulated across every call to
+    /// [`reader().recv`](RxStreamReader::recv)/[`recv_status`](RxStreamReader::recv_status)
+    /// on this stream so far.
+    pub fn stats(&self) -> RxStats {
+        self.stats
+    }
+
     pub fn channels(&self) -> usize {
         self.channels
     }
 
+    /// The over-the-wire format this stream was opened with, or `None` if
+    /// it was left to UHD to choose automatically (see
+    /// [`RxStreamBuilder::with_otw_format`]).
+    pub fn otw_format(&self) -> Option<OtwFormat> {
+        self.otw_format
+    }
+
     #[must_use = "commands must be sent to start the stream"]
     pub fn start_command(&self) -> RxStartCommand<T> {
         RxStartCommand::new(self)
@@ -186,6 +229,241 @@ where
     pub fn reader(&mut self) -> RxStreamReader<T> {
         RxStreamReader::new(self)
     }
+
+    /// Run a self-contained receive loop that automatically restarts the
+    /// stream after an overflow, until `on_samples` returns `false`.
+    ///
+    /// This starts the stream in continuous mode, repeatedly calls
+    /// [`recv`](RxStreamReader::recv) into `buff`, and invokes `on_samples`
+    /// with the buffer and the number of samples received per channel
+    /// after each successful receive. On an [`RxErrorCode::Overflow`], the
+    /// stream is stopped and restarted automatically and the iteration
+    /// that detected it is skipped; `on_samples` is not called for it.
+    /// The stream is always stopped before this method returns.
+    ///
+    /// Returns [`RxLoopMetrics`] summarizing samples received and
+    /// overflow/restart counts over the run.
+    pub fn run_with_recovery<B, F>(
+        &mut self,
+        buff: &mut B,
+        mut on_samples: F,
+    ) -> Result<RxLoopMetrics>
+    where
+        B: SampleBuffer<T>,
+        F: FnMut(&B, usize) -> bool,
+    {
+        let mut metrics = RxLoopMetrics::default();
+        let mut metadata = RxMetadata::new();
+
+        self.start_command().send()?;
+        let result = (|| loop {
+            let samples = self
+                .reader()
+                .with_metadata_output(&mut metadata)
+                .recv(buff)?;
+
+            if let RxErrorCode::Overflow = metadata.error_code()? {
+                metrics.overflows += 1;
+                metrics.restarts += 1;
+                self.stop_now()?;
+                self.start_command().send()?;
+                continue;
+            }
+
+            metrics.samples_received += samples as u64;
+            if !on_samples(buff, samples) {
+                return Ok(());
+            }
+        })();
+        self.stop_now()?;
+
+        result?;
+        Ok(metrics)
+    }
+
+    /// Run a callback-driven receive loop, similar to an audio callback.
+    ///
+    /// This issues the start command described by `cfg`, then repeatedly
+    /// calls [`recv`](RxStreamReader::recv) into `buff` and invokes
+    /// `on_block` with the filled buffer and the metadata for that block.
+    /// [`RxErrorCode::Overflow`] is treated as a recoverable event rather
+    /// than a hard error: the stream is stopped and restarted and
+    /// `on_block` is not called for that iteration. All other metadata
+    /// (including [`RxErrorCode::Timeout`] and [`RxErrorCode::LateCommand`])
+    /// is passed through to `on_block` so callers can decide how to react.
+    /// The loop continues until `on_block` returns [`Flow::Stop`] or
+    /// `recv` returns an error; the stream is always stopped via
+    /// [`stop_now`](Self::stop_now) before this method returns.
+    pub fn run<B, F>(
+        &mut self,
+        cfg: RxRunConfig,
+        buff: &mut B,
+        mut on_block: F,
+    ) -> Result<RxLoopMetrics>
+    where
+        B: SampleBuffer<T>,
+        F: FnMut(&B, &RxMetadata) -> Flow,
+    {
+        let mut metrics = RxLoopMetrics::default();
+        let mut metadata = RxMetadata::new();
+
+        let mut start = self.start_command();
+        start.with_time(cfg.at_time);
+        if let Some((limit, and_done)) = cfg.limit {
+            start.with_limit(limit, and_done);
+        }
+        start.send()?;
+
+        let result = (|| loop {
+            let samples = self
+                .reader()
+                .with_timeout(cfg.timeout)
+                .with_metadata_output(&mut metadata)
+                .recv(buff)?;
+
+            if let RxErrorCode::Overflow = metadata.error_code()? {
+                metrics.overflows += 1;
+                metrics.restarts += 1;
+                self.stop_now()?;
+                let mut restart = self.start_command();
+                restart.with_time(cfg.at_time);
+                if let Some((limit, and_done)) = cfg.limit {
+                    restart.with_limit(limit, and_done);
+                }
+                restart.send()?;
+                continue;
+            }
+
+            metrics.samples_received += samples as u64;
+            match on_block(buff, &metadata) {
+                Flow::Continue => {}
+                Flow::Stop => return Ok(()),
+            }
+        })();
+        self.stop_now()?;
+
+        result?;
+        Ok(metrics)
+    }
+
+    /// Drives this stream from a dedicated worker thread using a
+    /// push-callback ("audio callback") model, instead of a manual
+    /// [`RxStreamReader::recv`] loop.
+    ///
+    /// The worker starts the stream in continuous mode, repeatedly
+    /// receives into a reused buffer built via `make_buffer` (typically an
+    /// [`ArrayBuffer`](crate::buffer::ArrayBuffer) sized to
+    /// `max_samples_per_channel()`), and invokes `on_block` with the
+    /// buffer and the metadata for that receive. `Flow::Stop` from
+    /// `on_block` ends the loop; any receive error is routed to `err_fn`
+    /// instead of tearing the worker down. Dropping (or calling
+    /// [`StreamHandle::stop`] on) the returned handle signals the worker
+    /// to stop the stream and join.
+    pub fn run_input<B, F, E>(
+        mut self,
+        make_buffer: impl FnOnce() -> B,
+        mut on_block: F,
+        mut err_fn: E,
+    ) -> StreamHandle
+    where
+        T: 'static,
+        B: SampleBuffer<T> + Send + 'static,
+        F: FnMut(&B, &RxMetadata) -> Flow + Send + 'static,
+        E: FnMut(UhdError) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+
+        let handle = thread::spawn(move || {
+            let mut buffer = make_buffer();
+            let mut metadata = RxMetadata::new();
+
+            if let Err(e) = self.start_command().send() {
+                err_fn(e);
+                return;
+            }
+
+            while !thread_stop.load(Ordering::Relaxed) {
+                let result = self
+                    .reader()
+                    .with_metadata_output(&mut metadata)
+                    .recv(&mut buffer);
+                match result {
+                    Ok(_) => match on_block(&buffer, &metadata) {
+                        Flow::Continue => {}
+                        Flow::Stop => break,
+                    },
+                    Err(e) => err_fn(e),
+                }
+            }
+
+            if let Err(e) = self.stop_now() {
+                err_fn(e);
+            }
+        });
+
+        StreamHandle::new(stop, handle)
+    }
+}
+
+/// Controls whether [`RxStream::run`] continues its receive loop or stops.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flow {
+    /// Keep receiving and invoking the callback.
+    Continue,
+    /// Stop the receive loop after this block.
+    Stop,
+}
+
+/// Configuration for [`RxStream::run`], covering the parameters of the
+/// start command it issues plus the per-`recv` timeout.
+#[derive(Clone, Copy, Debug)]
+pub struct RxRunConfig {
+    at_time: TimeSpec,
+    limit: Option<(usize, bool)>,
+    timeout: Duration,
+}
+
+impl Default for RxRunConfig {
+    fn default() -> Self {
+        Self {
+            at_time: TimeSpec::ZERO,
+            limit: None,
+            timeout: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RxRunConfig {
+    /// Start the stream at a specific time instead of immediately.
+    pub fn with_time(mut self, at_time: TimeSpec) -> Self {
+        self.at_time = at_time;
+        self
+    }
+
+    /// Stop automatically after `limit` samples per channel, as in
+    /// [`RxStartCommand::with_limit`].
+    pub fn with_limit(mut self, limit: usize, and_done: bool) -> Self {
+        self.limit = Some((limit, and_done));
+        self
+    }
+
+    /// Timeout for each individual `recv` call. Defaults to one second.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Metrics collected by [`RxStream::run_with_recovery`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RxLoopMetrics {
+    /// Total samples received per channel across the whole run.
+    pub samples_received: u64,
+    /// Number of overflows detected and automatically recovered from.
+    pub overflows: u64,
+    /// Number of times the stream was stopped and restarted.
+    pub restarts: u64,
 }
 
 pub struct RxStartCommand<'stream, T>
@@ -290,9 +568,14 @@ where
         self
     }
 
+    /// Receives into `buff`, which may hold anywhere from one sample up to
+    /// [`RxStream::max_samples_per_channel`] samples per channel; fewer
+    /// than that are perfectly valid and avoid over-allocating to the
+    /// hardware max on every call. Returns the number of samples per
+    /// channel actually written, which may be less than `buff.samples()`.
     pub fn recv(&mut self, buff: &mut impl SampleBuffer<T>) -> Result<usize> {
         if buff.channels() != self.stream.channels()
-            || buff.samples() != self.stream.samples_per_buffer
+            || buff.samples() > self.stream.samples_per_buffer
         {
             return Err(UhdError::Index);
         }
@@ -314,7 +597,9 @@ where
     }
 
     pub unsafe fn recv_unchecked(&mut self, buff: &mut impl SampleBuffer<T>) -> Result<usize> {
-        self.recv_raw(buff.as_mut_ptr(), buff.samples())
+        let result = self.recv_raw(buff.as_mut_ptr(), buff.samples());
+        buff.post_recv_sync();
+        result
     }
 
     pub unsafe fn recv_raw(
@@ -337,6 +622,106 @@ where
             self.one_packet,
             addr_of_mut!(received),
         ))?;
+
+        self.stream.stats.samples_received += received as u64;
+        if let Some(md) = self.metadata.as_deref() {
+            self.stream.stats.record(md);
+        }
+
         Ok(received)
     }
+
+    /// Like [`recv`](Self::recv), but reports `RxMetadata`'s error class
+    /// back as a structured [`RxStatus`] instead of surfacing it as a
+    /// fatal [`UhdError`]. This also feeds [`RxStream::stats`], so callers
+    /// running continuous captures can react to transient conditions
+    /// (overflows, late commands, ...) without unwinding the receive loop.
+    pub fn recv_status(&mut self, buff: &mut impl SampleBuffer<T>) -> Result<(usize, RxStatus)> {
+        if buff.channels() != self.stream.channels()
+            || buff.samples() > self.stream.samples_per_buffer
+        {
+            return Err(UhdError::Index);
+        }
+
+        let md = RxMetadata::new();
+        let mut received = 0;
+        try_uhd!(uhd_usrp_sys::uhd_rx_streamer_recv(
+            self.stream.handle().as_mut_ptr(),
+            buff.as_mut_ptr().cast(),
+            buff.samples(),
+            md.handle().as_mut_mut_ptr(),
+            self.timeout.unwrap_or(Duration::ZERO).as_secs_f64(),
+            self.one_packet,
+            addr_of_mut!(received),
+        ))?;
+        buff.post_recv_sync();
+
+        self.stream.stats.samples_received += received as u64;
+        self.stream.stats.record(&md);
+
+        Ok((received, RxStatus::from_metadata(&md)?))
+    }
+}
+
+/// Receive-health statistics accumulated by [`RxStreamReader`] across calls,
+/// exposed via [`RxStream::stats`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RxStats {
+    /// Total samples received per channel, across every call.
+    pub samples_received: u64,
+    /// Number of [`RxErrorCode::Overflow`] events seen.
+    pub overflows: u64,
+    /// Number of [`RxErrorCode::LateCommand`] events seen.
+    pub late_commands: u64,
+    /// Number of [`RxErrorCode::BrokenChain`] events seen.
+    pub broken_chains: u64,
+    /// Number of receives UHD reported as out of sequence.
+    pub out_of_sequence: u64,
+    /// Number of [`RxErrorCode::Timeout`] events seen.
+    pub timeouts: u64,
+}
+
+impl RxStats {
+    fn record(&mut self, md: &RxMetadata) {
+        match md.error_code() {
+            Ok(RxErrorCode::Overflow) => self.overflows += 1,
+            Ok(RxErrorCode::LateCommand) => self.late_commands += 1,
+            Ok(RxErrorCode::BrokenChain) => self.broken_chains += 1,
+            Ok(RxErrorCode::Timeout) => self.timeouts += 1,
+            _ => {}
+        }
+        if md.out_of_sequence() {
+            self.out_of_sequence += 1;
+        }
+    }
+}
+
+/// The error class reported by [`RxMetadata`] for a single [`RxStreamReader::recv_status`] call.
+#[derive(Clone, Copy, Debug)]
+pub enum RxStatus {
+    /// The receive completed with no error.
+    Ok,
+    /// Samples were dropped because the host couldn't keep up.
+    Overflow,
+    /// A stream command arrived at the device too late to be scheduled.
+    LateCommand,
+    /// The sample stream was interrupted, e.g. by a dropped packet.
+    BrokenChain,
+    /// No data arrived before the configured timeout.
+    Timeout,
+    /// Some other [`RxErrorCode`] not covered above.
+    Other(RxErrorCode),
+}
+
+impl RxStatus {
+    fn from_metadata(md: &RxMetadata) -> Result<Self> {
+        Ok(match md.error_code()? {
+            RxErrorCode::None => RxStatus::Ok,
+            RxErrorCode::Overflow => RxStatus::Overflow,
+            RxErrorCode::LateCommand => RxStatus::LateCommand,
+            RxErrorCode::BrokenChain => RxStatus::BrokenChain,
+            RxErrorCode::Timeout => RxStatus::Timeout,
+            other => RxStatus::Other(other),
+        })
+    }
 }
@@ -1,14 +1,27 @@
 use std::{
-    cell::Cell, collections::HashMap, ffi::CString, marker::PhantomData, ptr::addr_of_mut,
+    cell::Cell,
+    collections::HashMap,
+    ffi::CString,
+    marker::PhantomData,
+    ptr::addr_of_mut,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
     time::Duration,
 };
 
-use super::OtwFormat;
+use super::{callback::StreamHandle, Flow, OtwFormat};
 use crate::{
     error::try_uhd,
     ffi::OwnedHandle,
-    usrp::{metadata::TxMetadata, Usrp},
-    Result, Sample, SampleBuffer,
+    types::DeviceArgs,
+    usrp::{
+        metadata::{TxAsyncMetadata, TxMetadata},
+        Usrp,
+    },
+    Result, Sample, SampleBuffer, UhdError,
 };
 
 /// An owned handle for a USRP TX stream.
@@ -75,6 +88,21 @@ where
         self
     }
 
+    /// Merge a [`DeviceArgs`] builder's keys into this stream's arguments.
+    ///
+    /// This allows the same typed argument builder used for
+    /// [`Usrp::open`](crate::Usrp::open) and [`TuneRequest`](crate::types::TuneRequest)
+    /// to also be used for stream construction, instead of repeating
+    /// individual [`with_kwarg`](Self::with_kwarg) calls.
+    pub fn with_device_args(&mut self, args: DeviceArgs) -> &mut Self {
+        for kv in args.iter() {
+            if let Some((key, value)) = kv.split_once('=') {
+                self.args.insert(key.to_string(), value.to_string());
+            }
+        }
+        self
+    }
+
     /// Open the TX stream using the previously-specified arguments.
     #[must_use]
     pub fn open(&self) -> Result<TxStream<T>> {
@@ -120,6 +148,14 @@ pub struct TxStream<T: Sample> {
     samples_per_buffer: usize,
     channels: usize,
 
+    /// Reusable pointer scratch buffer for [`TxStreamWriter::send_contiguous`],
+    /// kept around so hot-loop sends don't allocate a fresh `Vec` every call.
+    ptr_scratch: Vec<*const T>,
+    /// A cached, never-mutated metadata handle used when the caller doesn't
+    /// supply their own via [`TxStreamWriter::with_metadata`], so sends
+    /// without custom metadata don't call `uhd_tx_metadata_make` every time.
+    default_metadata: OwnedHandle<uhd_usrp_sys::uhd_tx_metadata_t>,
+
     _unsync: PhantomData<Cell<T>>,
 }
 
@@ -138,6 +174,8 @@ impl<T: Sample> TxStream<T> {
             handle,
             samples_per_buffer: spb,
             channels,
+            ptr_scratch: Vec::with_capacity(channels),
+            default_metadata: TxMetadata::new().to_handle(),
             _unsync: PhantomData::default(),
         })
     }
@@ -157,6 +195,77 @@ impl<T: Sample> TxStream<T> {
     pub fn writer(&mut self) -> TxStreamWriter<T> {
         TxStreamWriter::new(self)
     }
+
+    /// Receive an asynchronous status message (e.g. a burst ack or an
+    /// underflow) reported about a previously sent packet.
+    ///
+    /// Returns `true` if a message was received into `metadata` before
+    /// `timeout` elapsed, `false` otherwise.
+    pub fn recv_async_msg(
+        &mut self,
+        metadata: &mut TxAsyncMetadata,
+        timeout: Duration,
+    ) -> Result<bool> {
+        let mut valid = false;
+        try_uhd!(unsafe {
+            uhd_usrp_sys::uhd_tx_streamer_recv_async_msg(
+                self.handle.as_mut_ptr(),
+                metadata.handle().as_mut_mut_ptr(),
+                timeout.as_secs_f64(),
+                addr_of_mut!(valid),
+            )
+        })?;
+        Ok(valid)
+    }
+
+    /// Drives this stream from a dedicated worker thread using a
+    /// pull-callback ("audio callback") model, instead of a manual
+    /// [`TxStreamWriter::send`] loop.
+    ///
+    /// The worker repeatedly fills a reused buffer of
+    /// `max_samples_per_channel()` samples per channel (one channel's block
+    /// after another, as in [`TxStreamWriter::send_contiguous`]) via
+    /// `data_fn`, then sends it.
+    /// `Flow::Stop` from `data_fn` ends the loop; any send error is routed
+    /// to `err_fn` instead of tearing the worker down. Dropping (or
+    /// calling [`StreamHandle::stop`] on) the returned handle signals the
+    /// worker to send a final empty end-of-burst buffer and join.
+    pub fn run_output<F, E>(mut self, mut data_fn: F, mut err_fn: E) -> StreamHandle
+    where
+        T: Default + Copy + Send + 'static,
+        F: FnMut(&mut [T], &mut TxMetadata) -> Flow + Send + 'static,
+        E: FnMut(UhdError) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+
+        let handle = thread::spawn(move || {
+            let mut buffer = vec![T::default(); self.channels * self.samples_per_buffer];
+            let mut metadata = TxMetadata::new();
+
+            while !thread_stop.load(Ordering::Relaxed) {
+                match data_fn(&mut buffer, &mut metadata) {
+                    Flow::Continue => {}
+                    Flow::Stop => break,
+                }
+                let mut writer = self.writer();
+                writer.with_metadata(&mut metadata);
+                if let Err(e) = writer.send_contiguous(&buffer) {
+                    err_fn(e);
+                }
+            }
+
+            let mut eob = TxMetadata::new();
+            eob.set_end_of_burst(true);
+            let mut writer = self.writer();
+            writer.with_metadata(&mut eob);
+            if let Err(e) = writer.send_contiguous(&[]) {
+                err_fn(e);
+            }
+        });
+
+        StreamHandle::new(stop, handle)
+    }
 }
 
 unsafe impl<T: Sample + Send> Send for TxStream<T> {}
@@ -206,23 +315,57 @@ where
         unsafe { self.send_raw(buff.as_ptr(), buff.samples()) }
     }
 
+    /// Send a single contiguous buffer laid out per-channel (channel `c`'s
+    /// samples occupy `interleaved[c * samples_per_channel..][..samples_per_channel]`).
+    ///
+    /// This avoids the `&[&[T]]` indirection of [`send`](Self::send) for the
+    /// common single- or strided-channel case: the per-channel pointers are
+    /// computed into the stream's reused scratch buffer instead of
+    /// allocating a fresh one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interleaved`'s length isn't evenly divisible by the
+    /// stream's channel count.
+    pub fn send_contiguous(&mut self, interleaved: &[T]) -> Result<usize> {
+        let channels = self.stream.channels;
+        assert_eq!(
+            interleaved.len() % channels,
+            0,
+            "interleaved buffer length must be a multiple of the channel count"
+        );
+        let samples_per_channel = interleaved.len() / channels;
+
+        self.stream.ptr_scratch.clear();
+        self.stream.ptr_scratch.extend(
+            (0..channels).map(|c| unsafe { interleaved.as_ptr().add(c * samples_per_channel) }),
+        );
+        let buff = self.stream.ptr_scratch.as_ptr();
+        unsafe { self.send_raw(buff, samples_per_channel) }
+    }
+
     pub unsafe fn send_raw(
         &mut self,
         buff: *const *const T,
         samples_per_channel: usize,
     ) -> Result<usize> {
         let mut sent = 0;
-        let metadata_handle = self
-            .metadata
-            .as_ref()
-            .map(|md| md.to_handle())
-            .unwrap_or_else(|| TxMetadata::new().to_handle());
+        // Only the no-custom-metadata case can reuse `default_metadata`:
+        // UHD's tx metadata handle has no in-place setters, only
+        // `uhd_tx_metadata_make`, so a caller-supplied `TxMetadata` still
+        // has to build a fresh handle on every send to reflect whatever
+        // per-packet fields (time spec, SOB/EOB) it carries.
+        let custom_handle = self.metadata.as_ref().map(|md| md.to_handle());
+        let metadata_ptr = match &custom_handle {
+            Some(handle) => handle.as_mut_mut_ptr(),
+            None => self.stream.default_metadata.as_mut_mut_ptr(),
+        };
         try_uhd!(unsafe {
             uhd_usrp_sys::uhd_tx_streamer_send(
                 self.stream.handle().as_mut_ptr(),
                 buff.cast_mut().cast(),
                 samples_per_channel,
-                metadata_handle.as_mut_mut_ptr(),
+                metadata_ptr,
                 self.timeout.unwrap_or_default().as_secs_f64(),
                 addr_of_mut!(sent),
             )
@@ -166,3 +166,10 @@ impl SubdevPair {
         &self.sd_name
     }
 }
+
+impl std::fmt::Display for SubdevPair {
+    /// Formats as `"db_name:sd_name"`, e.g. `"A:A"`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.db_name, self.sd_name)
+    }
+}